@@ -8,8 +8,12 @@ use std::path::PathBuf;
 
 #[expect(clippy::uninlined_format_args)]
 fn main() {
+    println!("cargo::rustc-check-cfg=cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)");
+    println!("cargo::rustc-check-cfg=cfg(LV_USE_FLEX)");
     println!("cargo::rustc-check-cfg=cfg(LV_USE_GRID)");
+    println!("cargo::rustc-check-cfg=cfg(LV_USE_LODEPNG)");
     println!("cargo::rustc-check-cfg=cfg(LV_USE_LOG)");
+    println!("cargo::rustc-check-cfg=cfg(LV_USE_SNAPSHOT)");
     println!(
         "cargo::rustc-check-cfg=cfg(LV_USE_STDLIB_MALLOC, values(\"BUILTIN\", \"CLIB\", \"CUSTOM\"))"
     );
@@ -36,9 +40,11 @@ fn main() {
         use crate::widgets::*;
         use crate::events::Event;
         use crate::subjects::Subject;
+        use crate::colors::Color;
         use crate::display::Display;
+        use crate::styles::{Part, Selector, State};
         #[allow(unused_imports)]
-        use crate::support::{Void, Mut, Const};
+        use crate::support::{Coord, Void, Mut, Const};
 
         #(#widgets_impl)*
     };
@@ -69,6 +75,16 @@ fn main() {
     )
     .unwrap();
 
+    // this check is needed to enable linear, radial and conical gradients
+    if lightvgl_sys::LV_USE_DRAW_SW_COMPLEX_GRADIENTS != 0 {
+        println!("cargo::rustc-cfg=LV_USE_DRAW_SW_COMPLEX_GRADIENTS");
+    }
+
+    // this check is needed to enable flex layout components
+    if lightvgl_sys::LV_USE_FLEX != 0 {
+        println!("cargo::rustc-cfg=LV_USE_FLEX");
+    }
+
     // this check is needed to enable lv_grid_fr wrapper
     if lightvgl_sys::LV_USE_GRID != 0 {
         println!("cargo::rustc-cfg=LV_USE_GRID");
@@ -79,6 +95,16 @@ fn main() {
         println!("cargo::rustc-cfg=LV_USE_LOG");
     }
 
+    // this check is needed to enable snapshots
+    if lightvgl_sys::LV_USE_SNAPSHOT != 0 {
+        println!("cargo::rustc-cfg=LV_USE_SNAPSHOT");
+    }
+
+    // this check is needed to enable PNG encoding and golden images
+    if lightvgl_sys::LV_USE_LODEPNG != 0 {
+        println!("cargo::rustc-cfg=LV_USE_LODEPNG");
+    }
+
     // this check is needed to warn of segmentation fault
     match lightvgl_sys::LV_USE_STDLIB_MALLOC {
         lightvgl_sys::LV_STDLIB_BUILTIN => {
@@ -11,7 +11,6 @@ use std::{
 };
 
 use embedded_graphics::{
-    draw_target::DrawTarget,
     pixelcolor::Rgb565,
     prelude::{Point, Size},
 };
@@ -27,9 +26,8 @@ use lv_bevy_ecs::{
     functions::*,
     info,
     input::{BufferStatus, InputDevice, InputEvent, InputState, Pointer},
-    styles::Style,
+    styles::{Part, Style},
     support::{Align, OpacityLevel},
-    sys::{lv_part_t_LV_PART_MAIN, lv_style_selector_t},
     widgets::{Arc, Button, Label, UnsafeLvglWorld},
 };
 use macro_rules_attribute::apply;
@@ -52,7 +50,7 @@ async fn main() {
     const VER_RES: usize = 240;
     const LINE_HEIGHT: usize = 16;
 
-    let mut sim_display =
+    let sim_display =
         SimulatorDisplay::<Rgb565>::new(Size::new(HOR_RES as u32, VER_RES as u32));
 
     let output_settings = OutputSettingsBuilder::new().scale(1).build();
@@ -65,22 +63,17 @@ async fn main() {
 
     info!("Simulator OK");
 
-    let mut display = Display::new(HOR_RES, VER_RES);
-
     let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
 
     info!("Display OK");
 
     let window = window_rc.clone();
-    display.register(buffer, move |refresh| {
-        //sim_display.draw_iter(refresh.as_pixels()).unwrap();
-        sim_display
-            .fill_contiguous(&refresh.rectangle, refresh.colors.iter().copied())
-            .unwrap();
-        if refresh.display.flush_is_last() {
-            window.borrow_mut().update(&sim_display);
-        }
-    });
+    let _display = Display::from_draw_target_with(
+        sim_display,
+        buffer,
+        move |sim_display| window.borrow_mut().update(sim_display),
+        |error| match error {},
+    );
 
     info!("Display Driver OK");
 
@@ -109,7 +102,7 @@ async fn main() {
             OpacityLevel::Cover as i32,
             |obj, val| {
                 #[expect(clippy::cast_sign_loss)]
-                obj.set_style_opa(val as u8, lv_part_t_LV_PART_MAIN as lv_style_selector_t);
+                obj.set_style_opa(val as u8, Part::Main);
             },
         );
 
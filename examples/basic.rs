@@ -19,14 +19,12 @@ use lv_bevy_ecs::{
     events::EventCode,
     functions::*,
     input::{BufferStatus, InputDevice, InputEvent, InputState, Pointer},
-    styles::Style,
+    styles::{Part, Style},
     support::{Align, OpacityLevel},
-    sys::{lv_part_t_LV_PART_MAIN, lv_style_selector_t},
     widgets::{Arc, Button, Label, LvglWorld},
 };
 
 use embedded_graphics::{
-    draw_target::DrawTarget,
     pixelcolor::Rgb565,
     prelude::{Point, Size},
 };
@@ -51,7 +49,7 @@ fn main() {
     const VER_RES: usize = 240;
     const LINE_HEIGHT: usize = 16;
 
-    let mut sim_display: SimulatorDisplay<Rgb565> =
+    let sim_display: SimulatorDisplay<Rgb565> =
         SimulatorDisplay::new(Size::new(HOR_RES as u32, VER_RES as u32));
 
     let output_settings = OutputSettingsBuilder::new().scale(1).build();
@@ -62,21 +60,15 @@ fn main() {
 
     error!("Random error");
 
-    let mut display = Display::new(HOR_RES, VER_RES);
-
     let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
 
-    display.register(
+    let _display = Display::from_draw_target_with(
+        sim_display,
         buffer,
-        share!(|refresh| {
-            //sim_display.draw_iter(refresh.as_pixels()).unwrap();
-            sim_display
-                .fill_contiguous(&refresh.rectangle, refresh.colors.iter().copied())
-                .unwrap();
-            if refresh.display.flush_is_last() {
-                take!(window_rc.clone()).borrow_mut().update(&sim_display);
-            }
+        share!(|sim_display| {
+            take!(window_rc.clone()).borrow_mut().update(sim_display);
         }),
+        |error| match error {},
     );
 
     let _touch_screen = InputDevice::<Pointer>::new(share!(|| get_touch_input(
@@ -105,7 +97,7 @@ fn main() {
             OpacityLevel::Cover as i32,
             |obj, val| {
                 #[expect(clippy::cast_sign_loss)]
-                obj.set_style_opa(val as u8, lv_part_t_LV_PART_MAIN as lv_style_selector_t);
+                obj.set_style_opa(val as u8, Part::Main);
             },
         );
 
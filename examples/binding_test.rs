@@ -19,12 +19,13 @@ use lv_bevy_ecs::{
     display::{Display, DrawBuffer},
     error,
     events::{Event, EventCode},
+    flex::FlexFlow,
     functions::*,
     info,
     input::{BufferStatus, InputDevice, InputEvent, InputState, Pointer},
-    styles::Style,
+    styles::{Part, State, Style},
     subjects::Subject,
-    support::{LV_SIZE_CONTENT, OpacityLevel, ToVoid, ToVoidMut},
+    support::{Coord, OpacityLevel, ToVoid, ToVoidMut},
     sys::{
         LV_ANIM_REPEAT_INFINITE, LV_GRID_CONTENT, LV_GRID_TEMPLATE_LAST, LV_SYMBOL_FILE,
         lv_align_t_LV_ALIGN_BOTTOM_RIGHT, lv_anim_path_ease_out, lv_anim_set_path_cb,
@@ -32,13 +33,11 @@ use lv_bevy_ecs::{
         lv_buttonmatrix_ctrl_t_LV_BUTTONMATRIX_CTRL_DISABLED,
         lv_chart_axis_t_LV_CHART_AXIS_PRIMARY_X, lv_chart_type_t_LV_CHART_TYPE_BAR,
         lv_chart_type_t_LV_CHART_TYPE_LINE, lv_color_format_t_LV_COLOR_FORMAT_RGB565,
-        lv_draw_buf_align, lv_draw_image_dsc_t, lv_draw_line_dsc_t,
-        lv_flex_flow_t_LV_FLEX_FLOW_COLUMN, lv_font_montserrat_24,
+        lv_draw_buf_align, lv_draw_image_dsc_t, lv_draw_line_dsc_t, lv_font_montserrat_24,
         lv_grid_align_t_LV_GRID_ALIGN_CENTER, lv_grid_align_t_LV_GRID_ALIGN_START,
         lv_grid_align_t_LV_GRID_ALIGN_STRETCH, lv_layer_t, lv_obj_flag_t_LV_OBJ_FLAG_HIDDEN,
         lv_obj_flag_t_LV_OBJ_FLAG_IGNORE_LAYOUT, lv_observer_get_target, lv_observer_t,
-        lv_palette_t_LV_PALETTE_BLUE, lv_part_t_LV_PART_ITEMS, lv_state_t_LV_STATE_CHECKED,
-        lv_style_selector_t, lv_subject_get_int, lv_subject_t,
+        lv_palette_t_LV_PALETTE_BLUE, lv_subject_get_int, lv_subject_t,
     },
     widgets::{
         Button, Buttonmatrix, Canvas, Chart, Dropdown, Image, Label, LvglWorld, RawObj, Wdg, Widget,
@@ -46,7 +45,6 @@ use lv_bevy_ecs::{
 };
 
 use embedded_graphics::{
-    draw_target::DrawTarget,
     pixelcolor::Rgb565,
     prelude::{Point, Size},
 };
@@ -72,7 +70,7 @@ fn main() {
     const VER_RES: usize = 480;
     const LINE_HEIGHT: usize = 10;
 
-    let mut sim_display: SimulatorDisplay<Rgb565> =
+    let sim_display: SimulatorDisplay<Rgb565> =
         SimulatorDisplay::new(Size::new(HOR_RES as u32, VER_RES as u32));
 
     let output_settings = OutputSettingsBuilder::new().scale(1).build();
@@ -81,21 +79,15 @@ fn main() {
     window.update(&sim_display);
     let window_rc = Rc::new(RefCell::new(window));
 
-    let mut display = Display::new(HOR_RES, VER_RES);
-
     let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
 
-    display.register(
+    let _display = Display::from_draw_target_with(
+        sim_display,
         buffer,
-        share!(|refresh| {
-            //sim_display.draw_iter(refresh.as_pixels()).unwrap();
-            sim_display
-                .fill_contiguous(&refresh.rectangle, refresh.colors.iter().copied())
-                .unwrap();
-            if refresh.display.flush_is_last() {
-                take!(window_rc.clone()).borrow_mut().update(&sim_display);
-            }
+        share!(|sim_display| {
+            take!(window_rc.clone()).borrow_mut().update(sim_display);
         }),
+        |error| match error {},
     );
 
     // Register a new input device that's capable of reading the current state of the input
@@ -217,9 +209,9 @@ fn create_ui(world_rc: Rc<RefCell<LvglWorld>>) {
         1,
     );
 
-    label.set_style_bg_opa(OpacityLevel::Percent70 as u8, 0);
-    label.set_style_bg_color(c1, 0);
-    label.set_style_text_color(c2, 0);
+    label.set_style_bg_opa(OpacityLevel::Percent70 as u8, Part::Main);
+    label.set_style_bg_color(c1, Part::Main);
+    label.set_style_text_color(c2, Part::Main);
     let mut label_entity = world.spawn((DynamicLabel, label.into_inner()));
     label_entity.insert(style_big_font.clone());
 
@@ -263,8 +255,7 @@ fn create_ui(world_rc: Rc<RefCell<LvglWorld>>) {
 
     let mut btnmatrix_entity = world.spawn(btnmatrix.into_inner());
 
-    let mut style_big_font_2 =
-        Style::new((lv_part_t_LV_PART_ITEMS | lv_state_t_LV_STATE_CHECKED) as lv_style_selector_t);
+    let mut style_big_font_2 = Style::new(Part::Items | State::CHECKED);
 
     unsafe {
         style_big_font_2.set_text_font(&lv_font_montserrat_24);
@@ -281,7 +272,7 @@ fn create_ui(world_rc: Rc<RefCell<LvglWorld>>) {
         0,
         2,
     );
-    cont.set_flex_flow(lv_flex_flow_t_LV_FLEX_FLOW_COLUMN);
+    cont.set_flex_flow(FlexFlow::Column.into());
     let cont_entity = world.spawn(cont);
     let cont_id = cont_entity.id();
 
@@ -299,7 +290,7 @@ fn create_ui(world_rc: Rc<RefCell<LvglWorld>>) {
                 OpacityLevel::Percent50 as i32,
                 |widget, value| {
                     #[expect(clippy::cast_sign_loss)]
-                    widget.set_style_opa(value as u8, 0);
+                    widget.set_style_opa(value as u8, Part::Main);
                 },
             );
             unsafe {
@@ -326,7 +317,7 @@ fn create_ui(world_rc: Rc<RefCell<LvglWorld>>) {
             let btn_label: &mut Label<Wdg> = btn_label_widget.downcast_mut().unwrap();
 
             btn_label.set_text(c"A multi-line text with a ° symbol");
-            btn_label.set_width(lv_pct(100));
+            btn_label.set_width(Coord::Pct(100));
         }
 
         if i == 3 {
@@ -411,7 +402,7 @@ fn create_ui(world_rc: Rc<RefCell<LvglWorld>>) {
 
 fn opa_anim_cb(widget: &mut Wdg, value: i32) {
     #[expect(clippy::cast_sign_loss)]
-    widget.set_style_opa(value as u8, 0);
+    widget.set_style_opa(value as u8, Part::Main);
 }
 
 fn chart_type_observer_cb(observer: *mut lv_observer_t, subject: *mut lv_subject_t) {
@@ -448,7 +439,7 @@ fn buttonmatrix_event_cb(world: &mut World, event: &mut Event) {
 
 fn list_button_create(world: &mut World, parent: Entity) -> Entity {
     let mut btn = Button::new();
-    btn.set_size(lv_pct(100), LV_SIZE_CONTENT as i32);
+    btn.set_size(Coord::Pct(100), Coord::Content);
 
     let btn_id = world.spawn(btn.into_inner()).id();
     let mut parent = world.entity_mut(parent);
@@ -13,15 +13,14 @@ use std::{
 
 use lv_bevy_ecs::{
     animation::Animation,
-    display::{Display, DrawBuffer},
+    display::{Display, DrawBuffer, RenderMode},
     error,
     events::EventCode,
     functions::*,
     info,
     input::{BufferStatus, InputDevice, InputEvent, InputState, Pointer},
-    styles::Style,
+    styles::{Part, Style},
     support::{Align, OpacityLevel},
-    sys::{lv_part_t_LV_PART_MAIN, lv_style_selector_t},
     trace,
     widgets::{Arc, Button, Label, Widget},
 };
@@ -78,6 +77,8 @@ fn main() {
 
     display.register(
         buffer,
+        None,
+        RenderMode::Partial,
         share!(|refresh| {
             //sim_display.draw_iter(refresh.as_pixels()).unwrap();
             trace!("Flushing to display");
@@ -116,7 +117,7 @@ fn main() {
             OpacityLevel::Transparent as i32,
             OpacityLevel::Cover as i32,
             |obj, val| {
-                obj.set_style_opa(val as u8, lv_part_t_LV_PART_MAIN as lv_style_selector_t);
+                obj.set_style_opa(val as u8, Part::Main);
             },
         );
 
@@ -149,7 +150,7 @@ fn main() {
         style.set_align(Align::TopLeft.into());
         style.set_bg_color(lv_color_make(255, 0, 0));
         unsafe {
-            button.add_style(&mut style, lv_part_t_LV_PART_MAIN as lv_style_selector_t);
+            button.add_style(&mut style, Part::Main);
         }
 
         Button::leak(button);
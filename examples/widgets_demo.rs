@@ -20,7 +20,6 @@ use lv_bevy_ecs::{
 };
 
 use embedded_graphics::{
-    draw_target::DrawTarget,
     pixelcolor::Rgb565,
     prelude::{Point, Size},
 };
@@ -39,7 +38,7 @@ fn main() {
     const VER_RES: usize = 480;
     const LINE_HEIGHT: usize = 16;
 
-    let mut sim_display: SimulatorDisplay<Rgb565> =
+    let sim_display: SimulatorDisplay<Rgb565> =
         SimulatorDisplay::new(Size::new(HOR_RES as u32, VER_RES as u32));
 
     let output_settings = OutputSettingsBuilder::new().scale(1).build();
@@ -51,23 +50,17 @@ fn main() {
     info!("SIMULATOR OK");
     error!("Random error");
 
-    let mut display = Display::new(HOR_RES, VER_RES);
-
     let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
 
     info!("Display OK");
 
-    display.register(
+    let _display = Display::from_draw_target_with(
+        sim_display,
         buffer,
-        share!(|refresh| {
-            //sim_display.draw_iter(refresh.as_pixels()).unwrap();
-            sim_display
-                .fill_contiguous(&refresh.rectangle, refresh.colors.iter().copied())
-                .unwrap();
-            if refresh.display.flush_is_last() {
-                take!(window_rc.clone()).borrow_mut().update(&sim_display);
-            }
+        share!(|sim_display| {
+            take!(window_rc.clone()).borrow_mut().update(sim_display);
         }),
+        |error| match error {},
     );
 
     info!("Display Driver OK");
@@ -27,14 +27,31 @@ const FUNCTION_BLACKLIST: &[&str] = &[
     "lv_label_set_text_vfmt",       // cannot cross-compile
     "lv_obj_report_style_change",   // first parameter is not obj
     "lv_style_transition_dsc_init", // first parameter is not style
+    "lv_style_set_transition",      // implemented manually
+    "lv_style_set_bg_grad",         // implemented manually
     "lv_keyboard_def_event_cb",     // first parameter is not keyboard
     "lv_subject_add_observer_obj",  // implemented manually
     "lv_display_set_rotation",      // implemented manually
     "lv_display_set_flush_wait_cb", // implemented manually
     "lv_display_flush_ready",       // called automatically
+    "lv_display_delete",            // deleted on drop
+    "lv_display_set_default",       // implemented manually
+    "lv_display_set_theme",         // implemented manually
+    "lv_display_set_user_data",     // holds the Rust callbacks
     "lv_sysmon_create",             // lv_display_t pulls this in
 ];
 
+/// Properties whose setters take `impl Into<Coord>` instead of `i32`
+/// (`lv_obj_set_*`, `lv_obj_set_style_*` and `lv_style_set_*`)
+#[rustfmt::skip]
+const COORD_PROPERTIES: &[&str] = &[
+    "x", "y", "pos", "width", "height", "size",
+    "min_width", "max_width", "min_height", "max_height",
+    "pad_top", "pad_bottom", "pad_left", "pad_right", "pad_row", "pad_column",
+    "pad_all", "pad_hor", "pad_ver", "pad_gap",
+    "translate_x", "translate_y",
+];
+
 const OBJECT_WHITELIST: &[&str] = &[
     "lv_obj_t",
     "lv_style_t",
@@ -381,14 +398,23 @@ impl Rusty for LvFunc {
     }
 }
 
+fn is_coord_setter(name: &str) -> bool {
+    ["lv_obj_set_style_", "lv_obj_set_", "lv_style_set_"]
+        .iter()
+        .filter_map(|prefix| name.strip_prefix(prefix))
+        .any(|property| COORD_PROPERTIES.contains(&property))
+}
+
 impl From<ForeignItemFn> for LvFunc {
     fn from(ffi: ForeignItemFn) -> Self {
         let ret = match ffi.sig.output {
             ReturnType::Default => None,
             ReturnType::Type(_, typ) => Some(typ.into()),
         };
+        let name = ffi.sig.ident.to_string();
+        let coord_setter = is_coord_setter(&name);
         Self::new(
-            ffi.sig.ident.to_string(),
+            name,
             ffi.sig
                 .inputs
                 .iter()
@@ -401,6 +427,13 @@ impl From<ForeignItemFn> for LvFunc {
                     }
                 })
                 .map(Into::into)
+                .map(|mut arg: LvArg| {
+                    // the coordinates are plain integers in the bindings
+                    if coord_setter && arg.typ.literal_name == "i32" {
+                        arg.typ = LvType::new("lv_coord_t".to_string());
+                    }
+                    arg
+                })
                 .collect::<Vec<LvArg>>(),
             ret,
             ffi.attrs
@@ -519,6 +552,22 @@ impl LvArg {
             quote! {
                 #ident.raw_mut()
             }
+        } else if self.typ.is_style_selector() {
+            quote! {
+                Into::<Selector>::into(#ident).bits()
+            }
+        } else if self.typ.is_part() || self.typ.is_state() {
+            quote! {
+                #ident.into()
+            }
+        } else if self.typ.is_color() {
+            quote! {
+                Into::<Color>::into(#ident).into()
+            }
+        } else if self.typ.is_coord() {
+            quote! {
+                Into::<Coord>::into(#ident).into()
+            }
         } else if self.typ.is_mut_void() {
             quote! {#ident.as_c_void()}
         } else if self.typ.is_const_void() {
@@ -630,6 +679,26 @@ impl LvType {
         self.literal_name == "* const lv_display_t"
     }
 
+    pub fn is_style_selector(&self) -> bool {
+        self.literal_name == "lv_style_selector_t"
+    }
+
+    pub fn is_part(&self) -> bool {
+        self.literal_name == "lv_part_t"
+    }
+
+    pub fn is_state(&self) -> bool {
+        self.literal_name == "lv_state_t"
+    }
+
+    pub fn is_color(&self) -> bool {
+        self.literal_name == "lv_color_t"
+    }
+
+    pub fn is_coord(&self) -> bool {
+        self.literal_name == "lv_coord_t"
+    }
+
     pub fn is_pointer(&self) -> bool {
         self.literal_name.starts_with('*')
     }
@@ -690,6 +759,16 @@ impl Rusty for LvType {
             quote!(&Display)
         } else if self.is_mut_display() {
             quote!(&mut Display)
+        } else if self.is_style_selector() {
+            quote!(impl Into<Selector>)
+        } else if self.is_part() {
+            quote!(Part)
+        } else if self.is_state() {
+            quote!(State)
+        } else if self.is_color() {
+            quote!(impl Into<Color>)
+        } else if self.is_coord() {
+            quote!(impl Into<Coord>)
         } else if self.is_mut_void() {
             quote!(Void<Mut>)
         } else if self.is_const_void() {
@@ -1113,6 +1192,133 @@ mod test {
         assert_eq!(code.to_string(), expected_code.to_string());
     }
 
+    #[test]
+    fn generate_method_wrapper_with_style_selector_as_argument() {
+        let bindgen_code = quote! {
+            unsafe extern "C" {
+                pub fn lv_obj_set_style_opa(obj: *mut lv_obj_t, value: lv_opa_t, selector: lv_style_selector_t);
+                pub fn lv_obj_get_style_opa(obj: *const lv_obj_t, part: lv_part_t) -> lv_opa_t;
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+        let parent_widget = LvWidget {
+            name: "obj".to_string(),
+            methods: vec![],
+        };
+
+        let code = cg.get(0).unwrap().code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            impl Wdg {
+                #[inline]
+                pub fn set_style_opa(&mut self, value: lv_opa_t, selector: impl Into<Selector>) {
+                    unsafe {
+                        lightvgl_sys::lv_obj_set_style_opa(
+                            self.raw_mut(),
+                            value,
+                            Into::<Selector>::into(selector).bits()
+                        )
+                    }
+                }
+            }
+        };
+        assert_eq!(code.to_string(), expected_code.to_string());
+
+        let code = cg.get(1).unwrap().code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            impl Wdg {
+                #[inline]
+                pub fn get_style_opa(&self, part: Part) -> lv_opa_t {
+                    unsafe {
+                        lightvgl_sys::lv_obj_get_style_opa(
+                            self.raw(),
+                            part.into()
+                        )
+                    }
+                }
+            }
+        };
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
+    #[test]
+    fn generate_method_wrapper_with_color_as_argument() {
+        let bindgen_code = quote! {
+            unsafe extern "C" {
+                pub fn lv_style_set_bg_color(style: *mut lv_style_t, value: lv_color_t);
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+        let parent_widget = LvWidget {
+            name: "style".to_string(),
+            methods: vec![],
+        };
+
+        let code = cg.get(0).unwrap().code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            impl Style {
+                #[inline]
+                pub fn set_bg_color(&mut self, value: impl Into<Color>) {
+                    unsafe {
+                        lightvgl_sys::lv_style_set_bg_color(
+                            self.raw_mut(),
+                            Into::<Color>::into(value).into()
+                        )
+                    }
+                }
+            }
+        };
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
+    #[test]
+    fn generate_method_wrapper_with_coords_as_arguments() {
+        let bindgen_code = quote! {
+            unsafe extern "C" {
+                pub fn lv_obj_set_size(obj: *mut lv_obj_t, w: i32, h: i32);
+                pub fn lv_obj_set_style_pad_top(obj: *mut lv_obj_t, value: i32, selector: lv_style_selector_t);
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+        let parent_widget = LvWidget {
+            name: "obj".to_string(),
+            methods: vec![],
+        };
+
+        let code = cg.get(0).unwrap().code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            impl Wdg {
+                #[inline]
+                pub fn set_size(&mut self, w: impl Into<Coord>, h: impl Into<Coord>) {
+                    unsafe {
+                        lightvgl_sys::lv_obj_set_size(
+                            self.raw_mut(),
+                            Into::<Coord>::into(w).into(),
+                            Into::<Coord>::into(h).into()
+                        )
+                    }
+                }
+            }
+        };
+        assert_eq!(code.to_string(), expected_code.to_string());
+
+        let code = cg.get(1).unwrap().code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            impl Wdg {
+                #[inline]
+                pub fn set_style_pad_top(&mut self, value: impl Into<Coord>, selector: impl Into<Selector>) {
+                    unsafe {
+                        lightvgl_sys::lv_obj_set_style_pad_top(
+                            self.raw_mut(),
+                            Into::<Coord>::into(value).into(),
+                            Into::<Selector>::into(selector).bits()
+                        )
+                    }
+                }
+            }
+        };
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
     #[test]
     fn generate_method_wrapper_with_mut_void_pointer_as_argument() {
         let bindgen_code = quote! {
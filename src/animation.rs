@@ -7,7 +7,8 @@
 //! # use lv_bevy_ecs::animation::Animation;
 //! # use lv_bevy_ecs::functions::*;
 //! # use lv_bevy_ecs::support::OpacityLevel;
-//! # use lv_bevy_ecs::sys::{lv_part_t_LV_PART_MAIN, lv_anim_count_running};
+//! # use lv_bevy_ecs::styles::Part;
+//! # use lv_bevy_ecs::sys::lv_anim_count_running;
 //! # use lv_bevy_ecs::widgets::{Button, LvglWorld, Wdg};
 //! #
 //! # lv_bevy_ecs::setup_test_display!();
@@ -20,7 +21,7 @@
 //!     OpacityLevel::Transparent as i32,
 //!     OpacityLevel::Cover as i32,
 //!     |obj, val| {
-//!         obj.set_style_opa(val as u8, lv_part_t_LV_PART_MAIN);
+//!         obj.set_style_opa(val as u8, Part::Main);
 //!     },
 //! );
 //! let mut button_entity = world.spawn((button.into_inner(), anim));
@@ -36,6 +37,37 @@ use bevy_ecs::{component::Component, lifecycle::HookContext, world::DeferredWorl
 
 use crate::widgets::{RawObj, Wdg, Widget};
 
+/// Easing functions of animations and style transitions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum AnimPath {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Overshoots the end value, then goes back
+    Overshoot,
+    /// Bounces back from the end value a few times
+    Bounce,
+    /// Jumps to the end value at the end
+    Step,
+}
+
+impl From<AnimPath> for lightvgl_sys::lv_anim_path_cb_t {
+    #[inline]
+    fn from(value: AnimPath) -> Self {
+        Some(match value {
+            AnimPath::Linear => lightvgl_sys::lv_anim_path_linear,
+            AnimPath::EaseIn => lightvgl_sys::lv_anim_path_ease_in,
+            AnimPath::EaseOut => lightvgl_sys::lv_anim_path_ease_out,
+            AnimPath::EaseInOut => lightvgl_sys::lv_anim_path_ease_in_out,
+            AnimPath::Overshoot => lightvgl_sys::lv_anim_path_overshoot,
+            AnimPath::Bounce => lightvgl_sys::lv_anim_path_bounce,
+            AnimPath::Step => lightvgl_sys::lv_anim_path_step,
+        })
+    }
+}
+
 #[derive(Component)]
 #[component(on_insert = add_animation)]
 #[component(storage = "SparseSet")]
@@ -0,0 +1,191 @@
+//! # Bevy App
+//!
+//! [`LvglPlugin`] adds LVGL to a `bevy_app` [`App`]. It initializes LVGL, registers the observers
+//! that [`LvglWorld`](crate::widgets::LvglWorld) would register, runs the display and input setup
+//! hooks, adds [`sync_style_properties`] and [`ScreenNavigator::despawn_unloaded()`] to `PostUpdate`
+//! and replaces the runner with one that calls `lv_timer_handler()` after every update and
+//! sleeps according to [`NextTimerPeriod`].
+//!
+//! `no_std` has no clock, so the tick source and the sleep function have to be provided.
+//!
+//! ```no_run
+//! # use std::{sync::LazyLock, thread::sleep, time::Instant};
+//! # use bevy_app::App;
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use embedded_graphics::prelude::*;
+//! # use embedded_graphics_simulator::SimulatorDisplay;
+//! # use lv_bevy_ecs::app::LvglPlugin;
+//! # use lv_bevy_ecs::display::{Display, DrawBuffer};
+//! # use lv_bevy_ecs::input::{InputDevice, InputEvent, Pointer};
+//! #
+//! static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+//!
+//! fn create_display() -> Display {
+//!     const HOR_RES: usize = 320;
+//!     const VER_RES: usize = 240;
+//!     const LINE_HEIGHT: usize = 16;
+//!     let sim_display = SimulatorDisplay::<Rgb565>::new(Size::new(HOR_RES as u32, VER_RES as u32));
+//!     let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
+//!     Display::from_draw_target(sim_display, buffer)
+//! }
+//!
+//! App::new()
+//!     .add_plugins(
+//!         LvglPlugin::new()
+//!             .with_tick(|| START.elapsed().as_millis() as u32)
+//!             .with_sleep(sleep)
+//!             .with_display(create_display)
+//!             .with_setup(|_world| {
+//!                 let touch_screen =
+//!                     InputDevice::<Pointer>::new(|| InputEvent::new(Point::new(10, 20)));
+//!                 // the device is deleted when dropped, keep it for the lifetime of the app
+//!                 core::mem::forget(touch_screen);
+//!             }),
+//!     )
+//!     .run();
+//! ```
+
+use ::alloc::vec::Vec;
+use ::core::time::Duration;
+
+use bevy_app::{App, AppExit, Plugin, PluginsState, PostUpdate};
+use bevy_ecs::world::World;
+
+use crate::{
+    display::Display,
+    functions::{NextTimerPeriod, lv_timer_handler},
+    screens::ScreenNavigator,
+    styles::sync_style_properties,
+};
+
+/// Adds LVGL to an [`App`]
+pub struct LvglPlugin {
+    tick: Option<fn() -> u32>,
+    sleep: Option<fn(Duration)>,
+    max_sleep: Duration,
+    displays: Vec<fn() -> Display>,
+    setups: Vec<fn(&mut World)>,
+    runner: bool,
+}
+
+impl LvglPlugin {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the function that returns the elapsed milliseconds, see `lv_tick_set_cb()`
+    #[inline]
+    pub fn with_tick(mut self, tick: fn() -> u32) -> Self {
+        self.tick = Some(tick);
+        self
+    }
+
+    /// Sets the function the runner sleeps with, the runner does not sleep without it
+    ///
+    /// It is called with [`Duration::ZERO`] when a timer is already due,
+    /// e.g. to yield to other threads.
+    #[inline]
+    pub fn with_sleep(mut self, sleep: fn(Duration)) -> Self {
+        self.sleep = Some(sleep);
+        self
+    }
+
+    /// Sets the longest time the runner sleeps between two updates, 100 ms by default
+    ///
+    /// LVGL may not have a timer ready for seconds, but the other plugins still need to update.
+    #[inline]
+    pub fn with_max_sleep(mut self, max_sleep: Duration) -> Self {
+        self.max_sleep = max_sleep;
+        self
+    }
+
+    /// Creates a display when the plugin is built and spawns it with
+    /// [`LvglWorld::spawn_display()`](crate::widgets::LvglWorld::spawn_display)
+    #[inline]
+    pub fn with_display(mut self, display: fn() -> Display) -> Self {
+        self.displays.push(display);
+        self
+    }
+
+    /// Runs `setup` when the plugin is built, after LVGL and the displays have been initialized
+    ///
+    /// Use it to create input devices.
+    #[inline]
+    pub fn with_setup(mut self, setup: fn(&mut World)) -> Self {
+        self.setups.push(setup);
+        self
+    }
+
+    /// Keeps the runner of the app, `lv_timer_handler()` has to be called manually
+    #[inline]
+    pub fn without_runner(mut self) -> Self {
+        self.runner = false;
+        self
+    }
+}
+
+impl Default for LvglPlugin {
+    fn default() -> Self {
+        Self {
+            tick: None,
+            sleep: None,
+            max_sleep: Duration::from_millis(100),
+            displays: Vec::new(),
+            setups: Vec::new(),
+            runner: true,
+        }
+    }
+}
+
+impl Plugin for LvglPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(not(feature = "ctor"))]
+        crate::functions::lv_init();
+        if let Some(tick) = self.tick {
+            crate::functions::lv_tick_set_cb(tick);
+        }
+
+        let world = app.world_mut();
+        crate::widgets::add_observers(world);
+        for display in &self.displays {
+            crate::widgets::spawn_display(world, display());
+        }
+        for setup in &self.setups {
+            setup(world);
+        }
+        app.add_systems(
+            PostUpdate,
+            (sync_style_properties, ScreenNavigator::despawn_unloaded),
+        );
+
+        if self.runner {
+            let sleep = self.sleep;
+            let max_sleep = self.max_sleep;
+            app.set_runner(move |app| run(app, sleep, max_sleep));
+        }
+    }
+}
+
+fn run(mut app: App, sleep: Option<fn(Duration)>, max_sleep: Duration) -> AppExit {
+    if app.plugins_state() != PluginsState::Cleaned {
+        app.finish();
+        app.cleanup();
+    }
+    loop {
+        app.update();
+        if let Some(exit) = app.should_exit() {
+            return exit;
+        }
+        let period = match lv_timer_handler() {
+            NextTimerPeriod::Ready => Duration::ZERO,
+            NextTimerPeriod::AfterMs(next_timer_ms) => {
+                Duration::from_millis(next_timer_ms.get().into()).min(max_sleep)
+            }
+            NextTimerPeriod::Never => max_sleep,
+        };
+        if let Some(sleep) = sleep {
+            sleep(period);
+        }
+    }
+}
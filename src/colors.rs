@@ -0,0 +1,247 @@
+//! # Colors
+//!
+//! [`Color`] is a 24-bit RGB color that can be passed to every generated method that takes an
+//! `lv_color_t`. It can be created in `const` context, picked from a material design
+//! [`Palette`] or converted from the embedded-graphics color types.
+//!
+//! ```
+//! # use embedded_graphics::pixelcolor::{Rgb565, Rgb888};
+//! # use lv_bevy_ecs::colors::{Color, Hsv, Palette};
+//! # use lv_bevy_ecs::styles::{Part, Style};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! const ACCENT: Color = Color::hex(0x2196F3);
+//!
+//! let mut style = Style::default();
+//! style.set_bg_color(ACCENT);
+//! style.set_border_color(Palette::Blue.darken(2));
+//! style.set_text_color(Color::from(Rgb565::new(31, 63, 31)));
+//!
+//! let hover = ACCENT.mix(Color::WHITE, 200);
+//! assert_eq!(Color::from_hsv(Hsv { h: 0, s: 100, v: 100 }), Color::rgb(255, 0, 0));
+//! assert_eq!(Rgb888::from(Color::hex3(0xF80)), Rgb888::new(0xFF, 0x88, 0x00));
+//! # let _ = hover;
+//! ```
+
+use embedded_graphics::pixelcolor::{Gray8, GrayColor, Rgb565, Rgb888, RgbColor};
+use lightvgl_sys::{lv_color_hsv_t, lv_color_t, lv_opa_t, lv_palette_t};
+
+/// A 24-bit RGB color
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(0xFF, 0xFF, 0xFF);
+
+    #[inline]
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Creates a color from `0xRRGGBB`
+    #[inline]
+    pub const fn hex(hex: u32) -> Self {
+        Self::rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    /// Creates a color from `0xRGB`, e.g. `0xF80` is `0xFF8800`
+    #[inline]
+    pub const fn hex3(hex: u32) -> Self {
+        Self::rgb(
+            ((hex >> 8) & 0xF) as u8 * 0x11,
+            ((hex >> 4) & 0xF) as u8 * 0x11,
+            (hex & 0xF) as u8 * 0x11,
+        )
+    }
+
+    /// Returns the color as `0xRRGGBB`
+    #[inline]
+    pub fn to_hex(self) -> u32 {
+        (u32::from(self.red) << 16) | (u32::from(self.green) << 8) | u32::from(self.blue)
+    }
+
+    #[inline]
+    pub fn from_hsv(hsv: Hsv) -> Self {
+        unsafe { lightvgl_sys::lv_color_hsv_to_rgb(hsv.h, hsv.s, hsv.v).into() }
+    }
+
+    #[inline]
+    pub fn to_hsv(self) -> Hsv {
+        unsafe { lightvgl_sys::lv_color_to_hsv(self.into()).into() }
+    }
+
+    /// Mixes two colors, `ratio` 255 returns `self` and 0 returns `other`
+    #[inline]
+    pub fn mix(self, other: Self, ratio: lv_opa_t) -> Self {
+        unsafe { lightvgl_sys::lv_color_mix(self.into(), other.into(), ratio).into() }
+    }
+
+    /// Mixes the color with white, `level` 255 returns white
+    #[inline]
+    pub fn lighten(self, level: lv_opa_t) -> Self {
+        unsafe { lightvgl_sys::lv_color_lighten(self.into(), level).into() }
+    }
+
+    /// Mixes the color with black, `level` 255 returns black
+    #[inline]
+    pub fn darken(self, level: lv_opa_t) -> Self {
+        unsafe { lightvgl_sys::lv_color_darken(self.into(), level).into() }
+    }
+}
+
+impl From<lv_color_t> for Color {
+    #[inline]
+    fn from(value: lv_color_t) -> Self {
+        Self::rgb(value.red, value.green, value.blue)
+    }
+}
+
+impl From<Color> for lv_color_t {
+    #[inline]
+    fn from(value: Color) -> Self {
+        Self {
+            red: value.red,
+            green: value.green,
+            blue: value.blue,
+        }
+    }
+}
+
+impl From<Rgb888> for Color {
+    #[inline]
+    fn from(value: Rgb888) -> Self {
+        Self::rgb(value.r(), value.g(), value.b())
+    }
+}
+
+impl From<Color> for Rgb888 {
+    #[inline]
+    fn from(value: Color) -> Self {
+        Self::new(value.red, value.green, value.blue)
+    }
+}
+
+impl From<Rgb565> for Color {
+    #[inline]
+    fn from(value: Rgb565) -> Self {
+        Rgb888::from(value).into()
+    }
+}
+
+impl From<Color> for Rgb565 {
+    #[inline]
+    fn from(value: Color) -> Self {
+        Rgb888::from(value).into()
+    }
+}
+
+impl From<Gray8> for Color {
+    #[inline]
+    fn from(value: Gray8) -> Self {
+        Self::rgb(value.luma(), value.luma(), value.luma())
+    }
+}
+
+impl From<Color> for Gray8 {
+    /// Uses the same luma weights as embedded-graphics
+    #[inline]
+    fn from(value: Color) -> Self {
+        Rgb888::from(value).into()
+    }
+}
+
+/// A color in the HSV color space
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Hsv {
+    /// Hue in degrees, 0..360
+    pub h: u16,
+    /// Saturation in percent, 0..=100
+    pub s: u8,
+    /// Value in percent, 0..=100
+    pub v: u8,
+}
+
+impl From<lv_color_hsv_t> for Hsv {
+    #[inline]
+    fn from(value: lv_color_hsv_t) -> Self {
+        Self {
+            h: value.h,
+            s: value.s,
+            v: value.v,
+        }
+    }
+}
+
+impl From<Hsv> for Color {
+    #[inline]
+    fn from(value: Hsv) -> Self {
+        Self::from_hsv(value)
+    }
+}
+
+/// The material design palette of LVGL
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum Palette {
+    Red = lightvgl_sys::lv_palette_t_LV_PALETTE_RED,
+    Pink = lightvgl_sys::lv_palette_t_LV_PALETTE_PINK,
+    Purple = lightvgl_sys::lv_palette_t_LV_PALETTE_PURPLE,
+    DeepPurple = lightvgl_sys::lv_palette_t_LV_PALETTE_DEEP_PURPLE,
+    Indigo = lightvgl_sys::lv_palette_t_LV_PALETTE_INDIGO,
+    Blue = lightvgl_sys::lv_palette_t_LV_PALETTE_BLUE,
+    LightBlue = lightvgl_sys::lv_palette_t_LV_PALETTE_LIGHT_BLUE,
+    Cyan = lightvgl_sys::lv_palette_t_LV_PALETTE_CYAN,
+    Teal = lightvgl_sys::lv_palette_t_LV_PALETTE_TEAL,
+    Green = lightvgl_sys::lv_palette_t_LV_PALETTE_GREEN,
+    LightGreen = lightvgl_sys::lv_palette_t_LV_PALETTE_LIGHT_GREEN,
+    Lime = lightvgl_sys::lv_palette_t_LV_PALETTE_LIME,
+    Yellow = lightvgl_sys::lv_palette_t_LV_PALETTE_YELLOW,
+    Amber = lightvgl_sys::lv_palette_t_LV_PALETTE_AMBER,
+    Orange = lightvgl_sys::lv_palette_t_LV_PALETTE_ORANGE,
+    DeepOrange = lightvgl_sys::lv_palette_t_LV_PALETTE_DEEP_ORANGE,
+    Brown = lightvgl_sys::lv_palette_t_LV_PALETTE_BROWN,
+    BlueGrey = lightvgl_sys::lv_palette_t_LV_PALETTE_BLUE_GREY,
+    Grey = lightvgl_sys::lv_palette_t_LV_PALETTE_GREY,
+}
+
+impl Palette {
+    #[inline]
+    pub fn main(self) -> Color {
+        unsafe { lightvgl_sys::lv_palette_main(self.into()).into() }
+    }
+
+    /// A lighter shade, `level` is 1..=5
+    #[inline]
+    pub fn lighten(self, level: u8) -> Color {
+        unsafe { lightvgl_sys::lv_palette_lighten(self.into(), level).into() }
+    }
+
+    /// A darker shade, `level` is 1..=4
+    #[inline]
+    pub fn darken(self, level: u8) -> Color {
+        unsafe { lightvgl_sys::lv_palette_darken(self.into(), level).into() }
+    }
+}
+
+impl From<Palette> for lv_palette_t {
+    #[inline]
+    fn from(value: Palette) -> Self {
+        value as Self
+    }
+}
+
+impl From<Palette> for Color {
+    #[inline]
+    fn from(value: Palette) -> Self {
+        value.main()
+    }
+}
@@ -4,6 +4,8 @@
 //! The examples use [embedded-graphics-simulator](https://crates.io/crates/embedded-graphics-simulator) to try them on PC.
 //!
 //! ## Simulator
+//!
+//! Any embedded-graphics [`DrawTarget`] can be turned into a display with [`Display::from_draw_target()`].
 //! ```
 //! # use embedded_graphics::pixelcolor::Rgb565;
 //! # use embedded_graphics::prelude::*;
@@ -17,23 +19,208 @@
 //! const VER_RES: usize = 480;
 //! const LINE_HEIGHT: usize = 10;
 //!
+//! let sim_display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(HOR_RES as u32, VER_RES as u32));
+//! let buffer = DrawBuffer::<{ (HOR_RES * LINE_HEIGHT) as usize }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
+//! let display = Display::from_draw_target(sim_display, buffer);
+//!
+//! unsafe {
+//!     let mut default_display = Display::get_default();
+//!     assert_eq!(default_display.get_horizontal_resolution(), HOR_RES as i32);
+//!     assert_eq!(default_display.get_vertical_resolution(), VER_RES as i32);
+//!     assert_eq!(default_display.get_color_format(), Rgb565::as_lv_color_format_t());
+//! }
+//! ```
+//!
+//! ## Headless
+//!
+//! [`HeadlessDisplay`] renders into an in-memory framebuffer, so the output can be checked
+//! without a window, e.g. in CI.
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use embedded_graphics::prelude::*;
+//! # use lv_bevy_ecs::display::{DrawBuffer, HeadlessDisplay};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! lv_bevy_ecs::functions::lv_init();
+//! const HOR_RES: usize = 320;
+//! const VER_RES: usize = 240;
+//! const LINE_HEIGHT: usize = 16;
+//!
+//! let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
+//! let mut display = HeadlessDisplay::new(HOR_RES, VER_RES, buffer);
+//!
+//! let mut world = LvglWorld::default();
+//! let mut label = Label::new();
+//! label.set_text(c"Hello");
+//! world.spawn(label.into_inner());
+//!
+//! display.refresh();
+//! assert!(!display.take_dirty_regions().is_empty());
+//! assert_eq!(display.pixel(0, 0), Some(Rgb565::WHITE));
+//! assert_eq!(display.as_bytes().len(), HOR_RES * VER_RES * 2);
+//! ```
+//!
+//! ## Custom flush callback
+//!
+//! For full control over the flushing, register a callback that receives every [`DisplayRefresh`].
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use embedded_graphics::prelude::*;
+//! # use embedded_graphics_simulator::*;
+//! # use lv_bevy_ecs::display::{DrawBuffer, Display, RenderMode};
+//! #
+//! # lv_bevy_ecs::functions::lv_init();
+//! # const HOR_RES: usize = 800;
+//! # const VER_RES: usize = 480;
+//! # const LINE_HEIGHT: usize = 10;
+//! #
 //! let mut sim_display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(HOR_RES as u32, VER_RES as u32));
 //! let mut display = Display::new(HOR_RES, VER_RES);
 //!
 //! let buffer = DrawBuffer::<{ (HOR_RES * LINE_HEIGHT) as usize }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
-//! display.register(buffer, move |refresh| {
+//! display.register(buffer, None, RenderMode::Partial, move |refresh| {
 //!     // alternative (slower): sim_display.draw_iter(refresh.as_pixels()).unwrap();
 //!     sim_display
 //!         .fill_contiguous(&refresh.rectangle, refresh.colors.iter().cloned())
 //!         .unwrap();
 //! });
+//! ```
+//!
+//! ## Color formats
+//!
+//! The pixel type of the [DrawBuffer] selects the color format, it must match `LV_COLOR_DEPTH`:
+//!
+//! | Pixel type | Color format | `LV_COLOR_DEPTH` |
+//! | ---------- | ------------ | ---------------- |
+//! | [`BinaryColor`](embedded_graphics::pixelcolor::BinaryColor) | I1 | 1 |
+//! | [`Gray8`](embedded_graphics::pixelcolor::Gray8) | L8 | 8 |
+//! | [`Rgb565`](embedded_graphics::pixelcolor::Rgb565) | RGB565 | 16 |
+//! | [`Rgb565Swapped`](crate::support::Rgb565Swapped) | RGB565_SWAPPED | 16 |
+//! | [`Rgb888`](embedded_graphics::pixelcolor::Rgb888) | RGB888 | 24 |
+//! | [`Argb8888`](crate::support::Argb8888) | ARGB8888 | 32 |
+//! | [`Xrgb8888`](crate::support::Xrgb8888) | XRGB8888 | 32 |
+//!
+//! Monochrome pixels are packed 8 per byte, use [`DisplayRefresh::iter_colors()`] or [`DisplayRefresh::data`] to read them.
+//!
+//! ## Rotation
+//!
+//! [`Display::set_rotation()`] only swaps the resolution, the flushed pixels stay unrotated
+//! for controllers that can rotate in hardware. For other panels,
+//! [`Display::set_software_rotation()`] rotates each flushed area before the callback.
+//!
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use lv_bevy_ecs::display::{DrawBuffer, Display, DisplayRotation, RenderMode};
+//! #
+//! # lv_bevy_ecs::functions::lv_init();
+//! const HOR_RES: usize = 240;
+//! const VER_RES: usize = 320;
+//! const LINE_HEIGHT: usize = 16;
+//!
+//! let mut display = Display::new(HOR_RES, VER_RES);
+//! let buffer = DrawBuffer::<{ VER_RES * LINE_HEIGHT }, Rgb565>::new(VER_RES, LINE_HEIGHT);
+//! display.register(buffer, None, RenderMode::Partial, |refresh| {
+//!     // refresh.rectangle is in portrait panel coordinates
+//! });
+//! display.set_software_rotation(DisplayRotation::Deg90);
+//! ```
+//!
+//! ## Area rounding
+//!
+//! Controllers that can only be written in pages or pixel pairs need aligned flush areas.
 //!
-//! unsafe {
-//!     let mut default_display = Display::get_default();
-//!     assert_eq!(default_display.get_horizontal_resolution(), HOR_RES as i32);
-//!     assert_eq!(default_display.get_vertical_resolution(), VER_RES as i32);
-//!     assert_eq!(default_display.get_color_format(), Rgb565::as_lv_color_format_t());
-//! }
+//! ```
+//! # use lv_bevy_ecs::display::{Area, Display};
+//! #
+//! # lv_bevy_ecs::functions::lv_init();
+//! let mut display = Display::new(128, 64);
+//! display.set_area_rounder(Area::align_to_pages);
+//!
+//! let mut display = Display::new(320, 240);
+//! display.set_area_rounder(|area: &mut Area| area.align(4, 1));
+//! ```
+//!
+//! ## Double buffering
+//!
+//! A second buffer lets LVGL render the next chunk while the previous one is being flushed (e.g. with DMA).
+//! [`RenderMode::Direct`] and [`RenderMode::Full`] require both buffers to cover the whole screen.
+//!
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use lv_bevy_ecs::display::{DrawBuffer, Display, RenderMode};
+//! #
+//! # lv_bevy_ecs::functions::lv_init();
+//! const HOR_RES: usize = 320;
+//! const VER_RES: usize = 240;
+//!
+//! let mut display = Display::new(HOR_RES, VER_RES);
+//! let buffer = DrawBuffer::<{ HOR_RES * VER_RES }, Rgb565>::new(HOR_RES, VER_RES);
+//! let second_buffer = DrawBuffer::<{ HOR_RES * VER_RES }, Rgb565>::new(HOR_RES, VER_RES);
+//! display.register(buffer, Some(second_buffer), RenderMode::Full, |refresh| {
+//!     // push refresh.colors to the screen
+//! });
+//! ```
+//!
+//! ## Asynchronous flushing
+//!
+//! With [`Display::register_async()`] the flush only finishes when the [`FlushToken`] is completed,
+//! so the pixels can be transferred with DMA while LVGL renders into the second buffer.
+//!
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use lv_bevy_ecs::display::{DrawBuffer, Display, RenderMode};
+//! #
+//! # lv_bevy_ecs::functions::lv_init();
+//! const HOR_RES: usize = 320;
+//! const VER_RES: usize = 240;
+//! const LINE_HEIGHT: usize = 16;
+//!
+//! let mut display = Display::new(HOR_RES, VER_RES);
+//! let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
+//! let second_buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
+//! display.register_async(
+//!     buffer,
+//!     Some(second_buffer),
+//!     RenderMode::Partial,
+//!     |_refresh, token| {
+//!         // start the DMA transfer of refresh.colors here, then
+//!         // complete the token in the transfer-complete interrupt
+//!         token.complete();
+//!     },
+//!     || core::hint::spin_loop(),
+//! );
+//! ```
+//!
+//! ## Multiple displays
+//!
+//! A [`Display`] is deleted together with its screens when it is dropped. It is also a component,
+//! [`LvglWorld::spawn_display()`](crate::widgets::LvglWorld::spawn_display) spawns it with its active screen as a child entity.
+//! Widgets are created on the default display, use [`Display::with_default()`] to target another one.
+//!
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use lv_bevy_ecs::bevy::prelude::*;
+//! # use lv_bevy_ecs::display::{DrawBuffer, HeadlessDisplay, Display};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::functions::lv_init();
+//! let buffer = DrawBuffer::<{ 320 * 16 }, Rgb565>::new(320, 16);
+//! let main_display = HeadlessDisplay::new(320, 240, buffer);
+//!
+//! let mut world = LvglWorld::default();
+//! let mut status_display = Display::new(128, 32);
+//! let label = status_display.with_default(Label::new);
+//!
+//! let mut display_entity = world.spawn_display(status_display);
+//! let screen = display_entity.get::<Children>().unwrap()[0];
+//! let display_entity = display_entity.id();
+//! world.entity_mut(screen).with_child(label.into_inner());
+//!
+//! // created on the default (main) display
+//! world.spawn(Button::new().into_inner());
+//!
+//! // deletes the status display together with its screen and label
+//! world.despawn(display_entity);
 //! ```
 //!
 //! ## ESP32
@@ -46,31 +233,59 @@
 //! For code example on how to create a display on a DSI screen,
 //! check out [lvgl-bevy-demo-dsi](https://github.com/SakiiCode/lvgl-bevy-demo-dsi).
 
-use ::alloc::boxed::Box;
-use ::core::{marker::PhantomData, ptr::NonNull};
-use alloc::borrow::ToOwned;
+use ::alloc::{boxed::Box, rc::Rc, sync::Arc, vec, vec::Vec};
+use ::core::{
+    any::Any,
+    cell::{Ref, RefCell},
+    convert::Infallible,
+    ffi::c_void,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
+use bevy_ecs::component::Component;
 use embedded_graphics::{
     Pixel,
-    prelude::{PixelColor, Point, Size},
-    primitives::Rectangle,
+    draw_target::DrawTarget,
+    prelude::{Dimensions, OriginDimensions, PixelColor, Point, Size},
+    primitives::{PointsIter, Rectangle},
 };
 use lightvgl_sys::{
-    lv_color_format_t, lv_display_flush_ready, lv_display_get_user_data, lv_display_t,
-    lv_draw_buf_t,
+    lv_area_t, lv_color_format_t, lv_display_flush_ready, lv_display_get_user_data, lv_display_t,
+    lv_draw_buf_t, lv_obj_t,
 };
 
-use crate::support::LvglColorFormat;
+use crate::{support::LvglColorFormat, themes::Theme, widgets::Widget};
 
+/// An LVGL display, deleted when the owning handle created by [`Display::new()`] is dropped
+///
+/// It is also a [`Component`], so it can be spawned as an entity with its screens as children.
+/// The user data of the display holds its callbacks and must not be changed.
+#[derive(Component)]
 pub struct Display {
     raw: NonNull<lv_display_t>,
+    owned: bool,
 }
 
+unsafe impl Send for Display {}
+unsafe impl Sync for Display {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(windows, repr(i32))]
 #[cfg_attr(not(windows), repr(u32))]
 pub enum RenderMode {
+    /// Buffers can be smaller than the screen, changed areas are rendered chunk by chunk
+    ///
+    /// Each flush receives the rendered chunk only.
     Partial = lightvgl_sys::lv_display_render_mode_t_LV_DISPLAY_RENDER_MODE_PARTIAL,
+    /// Buffers must be screen-sized, only the changed areas are redrawn in place
+    ///
+    /// Each flush receives the whole buffer, so the pixels only need to be pushed
+    /// when [`flush_is_last()`](Display::flush_is_last) returns `true`.
     Direct = lightvgl_sys::lv_display_render_mode_t_LV_DISPLAY_RENDER_MODE_DIRECT,
+    /// Buffers must be screen-sized, the whole screen is redrawn on every refresh
     Full = lightvgl_sys::lv_display_render_mode_t_LV_DISPLAY_RENDER_MODE_FULL,
 }
 
@@ -106,76 +321,316 @@ impl Display {
                 ver_res.try_into().unwrap(),
             ))
             .unwrap();
-            Self { raw }
+            Self { raw, owned: true }
         }
     }
 
     /// Assigns a callback to `lv_display_set_flush_cb`
     /// ## Arguments
     ///  - `buffer` - [DrawBuffer] object that matches the [Display] color format
+    ///  - `second_buffer` - Optional [DrawBuffer] of the same size to render into while the other one is being flushed
+    ///  - `render_mode` - Specifies the `lv_display_render_mode_t`, [`RenderMode::Direct`] and [`RenderMode::Full`] need screen-sized buffers
     ///  - `callback` - Function or closure that pushes the pixels to the screen
-    #[expect(clippy::needless_pass_by_value)]
     pub fn register<F, const N: usize, C: LvglColorFormat>(
         &mut self,
         buffer: DrawBuffer<N, C>,
+        second_buffer: Option<DrawBuffer<N, C>>,
+        render_mode: RenderMode,
         callback: F,
     ) where
         F: FnMut(&mut DisplayRefresh<N, C>) + 'static,
     {
-        let cf = C::as_lv_color_format_t();
-        verify_color_format(cf);
-        unsafe {
-            lightvgl_sys::lv_display_set_draw_buffers(
-                self.raw_mut(),
-                buffer.raw.as_ptr(),
-                ::core::ptr::null_mut(),
-            );
-            lightvgl_sys::lv_display_set_flush_cb(
-                self.raw.as_ptr(),
-                Some(disp_flush_trampoline::<F, N, C>),
-            );
-            lightvgl_sys::lv_display_set_user_data(
-                self.raw.as_ptr(),
-                Box::into_raw(Box::new(callback)).cast(),
-            );
-        }
+        self.set_draw_buffers(buffer, second_buffer, render_mode);
+        self.set_flush_context(
+            disp_flush_trampoline::<F, N, C>,
+            FlushContext {
+                callback,
+                render_mode,
+            },
+        );
         crate::info!("Display Registered");
     }
 
     /// Assigns a callback to `lv_display_set_flush_cb`
     /// ## Arguments
     ///  * `buffer` - `[u8]` buffer that is exactly *N* bytes long
-    ///  * `render_mode` - Specifies the `lv_display_render_mode_t`
+    ///  * `second_buffer` - Optional `[u8]` buffer of the same size to render into while the other one is being flushed
+    ///  * `render_mode` - Specifies the `lv_display_render_mode_t`, [`RenderMode::Direct`] and [`RenderMode::Full`] need screen-sized buffers
     ///  * `callback` - Function or closure that pushes the pixels to the screen
     pub fn register_raw<F, const N: usize, C: LvglColorFormat>(
         &mut self,
         buffer: &'static mut [u8],
+        second_buffer: Option<&'static mut [u8]>,
         render_mode: RenderMode,
         callback: F,
     ) where
         F: FnMut(&mut DisplayRefresh<N, C>) + 'static,
     {
+        self.set_raw_buffers::<N, C>(buffer, second_buffer, render_mode);
+        self.set_flush_context(
+            disp_flush_trampoline::<F, N, C>,
+            FlushContext {
+                callback,
+                render_mode,
+            },
+        );
+        crate::info!("Display Registered");
+    }
+
+    /// Creates a display that owns an embedded-graphics [`DrawTarget`] and flushes into it
+    ///
+    /// The resolution is taken from the bounding box of `target`. Draw errors are logged.
+    pub fn from_draw_target<T, const N: usize, C>(target: T, buffer: DrawBuffer<N, C>) -> Self
+    where
+        T: DrawTarget<Color = C> + Dimensions + 'static,
+        C: LvglColorFormat,
+    {
+        Self::from_draw_target_with(
+            target,
+            buffer,
+            |_| {},
+            |_| crate::error!("Could not draw to the display"),
+        )
+    }
+
+    /// Creates a display that owns an embedded-graphics [`DrawTarget`] and flushes into it
+    /// ## Arguments
+    ///  - `target` - Any [`DrawTarget`] whose color matches the [DrawBuffer]
+    ///  - `buffer` - [DrawBuffer] object that matches the [Display] color format
+    ///  - `on_frame_end` - Called with the target after the last flush of a refresh, e.g. to update a window or swap framebuffers
+    ///  - `on_error` - Called with the error returned by the target
+    pub fn from_draw_target_with<T, const N: usize, C, FE, EH>(
+        mut target: T,
+        buffer: DrawBuffer<N, C>,
+        mut on_frame_end: FE,
+        mut on_error: EH,
+    ) -> Self
+    where
+        T: DrawTarget<Color = C> + Dimensions + 'static,
+        C: LvglColorFormat,
+        FE: FnMut(&mut T) + 'static,
+        EH: FnMut(T::Error) + 'static,
+    {
+        let size = target.bounding_box().size;
+        let mut display = Self::new(size.width as usize, size.height as usize);
+        display.register(buffer, None, RenderMode::Partial, move |refresh| {
+            if let Err(error) = target.fill_contiguous(&refresh.rectangle, refresh.iter_colors()) {
+                on_error(error);
+            }
+            if refresh.display.flush_is_last() {
+                on_frame_end(&mut target);
+            }
+        });
+        display
+    }
+
+    /// Same as [`register()`](Display::register), but the flush is only finished when the
+    /// [`FlushToken`] passed to `callback` is completed or dropped. This allows pushing
+    /// the pixels with DMA and completing the token from the transfer-complete interrupt.
+    ///
+    /// `wait` is called repeatedly while LVGL waits for the token, e.g. to poll the DMA
+    /// peripheral or put the CPU to sleep. It must not render anything.
+    pub fn register_async<F, W, const N: usize, C: LvglColorFormat>(
+        &mut self,
+        buffer: DrawBuffer<N, C>,
+        second_buffer: Option<DrawBuffer<N, C>>,
+        render_mode: RenderMode,
+        callback: F,
+        wait: W,
+    ) where
+        F: FnMut(&mut DisplayRefresh<N, C>, FlushToken) + 'static,
+        W: FnMut() + 'static,
+    {
+        self.set_draw_buffers(buffer, second_buffer, render_mode);
+        self.set_async_flush_context::<F, W, N, C>(callback, wait, render_mode);
+        crate::info!("Display Registered");
+    }
+
+    /// Same as [`register_raw()`](Display::register_raw), but the flush is only finished when
+    /// the [`FlushToken`] passed to `callback` is completed or dropped.
+    ///
+    /// See [`register_async()`](Display::register_async) for details.
+    pub fn register_raw_async<F, W, const N: usize, C: LvglColorFormat>(
+        &mut self,
+        buffer: &'static mut [u8],
+        second_buffer: Option<&'static mut [u8]>,
+        render_mode: RenderMode,
+        callback: F,
+        wait: W,
+    ) where
+        F: FnMut(&mut DisplayRefresh<N, C>, FlushToken) + 'static,
+        W: FnMut() + 'static,
+    {
+        self.set_raw_buffers::<N, C>(buffer, second_buffer, render_mode);
+        self.set_async_flush_context::<F, W, N, C>(callback, wait, render_mode);
+        crate::info!("Display Registered");
+    }
+
+    #[expect(clippy::needless_pass_by_value)]
+    fn set_draw_buffers<const N: usize, C: LvglColorFormat>(
+        &mut self,
+        buffer: DrawBuffer<N, C>,
+        second_buffer: Option<DrawBuffer<N, C>>,
+        render_mode: RenderMode,
+    ) {
+        let cf = C::as_lv_color_format_t();
+        verify_color_format(cf);
+        #[expect(clippy::arithmetic_side_effects)]
+        let screen_size = self.get_horizontal_resolution().cast_unsigned() as usize
+            * self.get_vertical_resolution().cast_unsigned() as usize;
+        verify_buffer_size(render_mode, N, screen_size);
+        let second_buffer_ptr =
+            second_buffer.map_or(::core::ptr::null_mut(), |second| second.raw.as_ptr());
+        unsafe {
+            lightvgl_sys::lv_display_set_draw_buffers(
+                self.raw_mut(),
+                buffer.raw.as_ptr(),
+                second_buffer_ptr,
+            );
+            lightvgl_sys::lv_display_set_render_mode(self.raw_mut(), render_mode.into());
+            self.destroy_replaced_draw_buffers();
+            for draw_buf in [buffer.raw.as_ptr(), second_buffer_ptr] {
+                if !draw_buf.is_null() {
+                    lightvgl_sys::lv_display_add_event_cb(
+                        self.raw_mut(),
+                        Some(destroy_draw_buf),
+                        lightvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+                        draw_buf.cast(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Destroys the draw buffers of a previous registration and removes their delete callbacks
+    fn destroy_replaced_draw_buffers(&mut self) {
+        unsafe {
+            let count = lightvgl_sys::lv_display_get_event_count(self.raw_mut());
+            for index in (0..count).rev() {
+                let dsc = lightvgl_sys::lv_display_get_event_dsc(self.raw_mut(), index);
+                if dsc.is_null() {
+                    continue;
+                }
+                let destroys_draw_buf = lightvgl_sys::lv_event_dsc_get_cb(dsc).is_some_and(|cb| {
+                    ::core::ptr::fn_addr_eq(cb, destroy_draw_buf as unsafe extern "C" fn(_))
+                });
+                if !destroys_draw_buf {
+                    continue;
+                }
+                let draw_buf = lightvgl_sys::lv_event_dsc_get_user_data(dsc);
+                lightvgl_sys::lv_display_delete_event(self.raw_mut(), index);
+                if !draw_buf.is_null() {
+                    lightvgl_sys::lv_draw_buf_destroy(draw_buf.cast());
+                }
+            }
+        }
+    }
+
+    fn set_raw_buffers<const N: usize, C: LvglColorFormat>(
+        &mut self,
+        buffer: &'static mut [u8],
+        second_buffer: Option<&'static mut [u8]>,
+        render_mode: RenderMode,
+    ) {
         let cf = C::as_lv_color_format_t();
         verify_color_format(cf);
         assert_eq!(buffer.len(), N);
+        let screen_size = unsafe {
+            let stride = lightvgl_sys::lv_draw_buf_width_to_stride(
+                self.get_horizontal_resolution().cast_unsigned(),
+                cf,
+            );
+            #[expect(clippy::arithmetic_side_effects)]
+            {
+                stride as usize * self.get_vertical_resolution().cast_unsigned() as usize
+                    + C::PALETTE_SIZE
+            }
+        };
+        verify_buffer_size(render_mode, N, screen_size);
+        let second_buffer_ptr = match second_buffer {
+            Some(second) => {
+                assert_eq!(second.len(), N, "Both buffers must have the same size");
+                second.as_mut_ptr().cast()
+            }
+            None => ::core::ptr::null_mut(),
+        };
         unsafe {
             lightvgl_sys::lv_display_set_buffers(
                 self.raw_mut(),
                 buffer.as_mut_ptr().cast(),
-                ::core::ptr::null_mut(),
+                second_buffer_ptr,
                 N.try_into().unwrap(),
                 render_mode.into(),
             );
-            lightvgl_sys::lv_display_set_flush_cb(
-                self.raw.as_ptr(),
-                Some(disp_flush_trampoline::<F, N, C>),
-            );
-            lightvgl_sys::lv_display_set_user_data(
-                self.raw.as_ptr(),
-                Box::into_raw(Box::new(callback)).cast(),
+        }
+    }
+
+    fn set_flush_context<T: 'static>(
+        &mut self,
+        trampoline: unsafe extern "C" fn(*mut lv_display_t, *const lv_area_t, *mut u8),
+        context: T,
+    ) {
+        self.context_mut().flush = Some(Box::new(context));
+        unsafe {
+            lightvgl_sys::lv_display_set_flush_cb(self.raw_mut(), Some(trampoline));
+            lightvgl_sys::lv_display_set_flush_wait_cb(self.raw_mut(), None);
+        }
+    }
+
+    fn set_async_flush_context<F, W, const N: usize, C: LvglColorFormat>(
+        &mut self,
+        callback: F,
+        wait: W,
+        render_mode: RenderMode,
+    ) where
+        F: FnMut(&mut DisplayRefresh<N, C>, FlushToken) + 'static,
+        W: FnMut() + 'static,
+    {
+        let display = self.raw;
+        self.set_flush_context(
+            disp_flush_async_trampoline::<F, W, N, C>,
+            AsyncFlushContext {
+                display,
+                callback,
+                wait,
+                render_mode,
+                state: Arc::new(FlushState {
+                    flushing: AtomicBool::new(false),
+                    alive: AtomicBool::new(true),
+                }),
+            },
+        );
+        unsafe {
+            lightvgl_sys::lv_display_set_flush_wait_cb(
+                self.raw_mut(),
+                Some(flush_wait_trampoline::<F, W>),
             );
         }
-        crate::info!("Display Registered");
+    }
+
+    /// Lets `callback` enlarge every invalidated area before it is rendered,
+    /// e.g. to match the page or pixel alignment required by the display controller
+    ///
+    /// [`Area::align_to_pages()`] and [`Area::align_to_even()`] can be passed directly.
+    /// The rounded area is clipped to the resolution of the display. Calling it again
+    /// replaces the previous callback.
+    pub fn set_area_rounder<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Area) + 'static,
+    {
+        let context = self.context_mut();
+        let previous = context.area_rounder.replace(Box::new(callback));
+        let context: *mut DisplayContext = context;
+        if previous.is_none() {
+            unsafe {
+                lightvgl_sys::lv_display_add_event_cb(
+                    self.raw_mut(),
+                    Some(area_rounder_trampoline),
+                    lightvgl_sys::lv_event_code_t_LV_EVENT_INVALIDATE_AREA,
+                    context.cast(),
+                );
+            }
+        }
     }
 
     #[inline]
@@ -185,12 +640,64 @@ impl Display {
         }
     }
 
+    /// Returns a handle to the default display, it does not delete the display when dropped
     #[inline]
     pub fn get_default() -> Self {
+        unsafe { Self::from_ptr(lightvgl_sys::lv_display_get_default()) }
+    }
+
+    /// Makes this the display that new widgets and screens are created on
+    #[inline]
+    pub fn set_default(&mut self) {
         unsafe {
-            Self {
-                raw: NonNull::new(lightvgl_sys::lv_display_get_default()).unwrap(),
-            }
+            lightvgl_sys::lv_display_set_default(self.raw_mut());
+        }
+    }
+
+    /// Runs `f` with this display set as default, so the widgets it creates are placed on its active screen
+    pub fn with_default<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        unsafe {
+            let previous = lightvgl_sys::lv_display_get_default();
+            self.set_default();
+            let result = f();
+            lightvgl_sys::lv_display_set_default(previous);
+            result
+        }
+    }
+
+    /// Creates a new screen on this display, load it with `lv_screen_load()`
+    pub fn create_screen(&mut self) -> Widget {
+        self.with_default(|| unsafe {
+            Widget::from_ptr(lightvgl_sys::lv_obj_create(::core::ptr::null_mut())).unwrap()
+        })
+    }
+
+    /// Sets the theme applied to the widgets created on this display
+    ///
+    /// The display keeps the theme alive until it is deleted or replaced. Widgets that already
+    /// exist keep their styles, use [`reapply_theme()`](crate::themes::reapply_theme) to switch
+    /// themes at runtime. The replaced theme is kept until then, its styles are still in use.
+    pub fn set_theme(&mut self, theme: &Theme) {
+        unsafe {
+            lightvgl_sys::lv_display_set_theme(self.raw_mut(), theme.raw_mut());
+        }
+        let context = self.context_mut();
+        if let Some(previous) = context.theme.replace(theme.clone())
+            && !previous.ptr_eq(theme)
+        {
+            context.replaced_themes.push(previous);
+        }
+    }
+
+    /// Returns the theme set with [`set_theme()`](Display::set_theme) and takes the themes it
+    /// replaced since the last call
+    pub(crate) fn take_themes(&mut self) -> (Option<Theme>, Vec<Theme>) {
+        match unsafe { display_context(self.raw_mut()) } {
+            Some(context) => (
+                context.theme.clone(),
+                ::core::mem::take(&mut context.replaced_themes),
+            ),
+            None => (None, Vec::new()),
         }
     }
 
@@ -201,6 +708,18 @@ impl Display {
         }
     }
 
+    /// Same as [`set_rotation()`](Display::set_rotation), but the pixels are also rotated
+    /// before they are passed to the flush callback, for panels that cannot rotate in hardware
+    ///
+    /// [`DisplayRefresh::rectangle`] is then in the coordinates of the panel.
+    /// Formats with less than 8 bits per pixel are not rotated.
+    pub fn set_software_rotation(&mut self, rotation: DisplayRotation) {
+        self.context_mut()
+            .rotation_buffer
+            .get_or_insert_with(Vec::new);
+        self.set_rotation(rotation);
+    }
+
     #[inline]
     pub fn raw(&self) -> *const lv_display_t {
         self.raw.as_ptr().cast_const()
@@ -211,14 +730,16 @@ impl Display {
         self.raw.as_ptr()
     }
 
+    /// Creates a handle from the given `*mut lv_display_t`, it does not delete the display when dropped
     #[inline]
     pub fn from_ptr(ptr: *mut lv_display_t) -> Self {
         Self {
             raw: NonNull::new(ptr).unwrap(),
+            owned: false,
         }
     }
 
-    /// Creates a new `Display` from the given `*mut lv_display_t`
+    /// Creates a new `Display` from the given `*mut lv_display_t`, it does not delete the display when dropped
     ///
     /// # Safety
     ///
@@ -228,9 +749,130 @@ impl Display {
         unsafe {
             Self {
                 raw: NonNull::new_unchecked(ptr),
+                owned: false,
             }
         }
     }
+
+    /// Returns the Rust data stored in the user data of the display, it is created on first use
+    fn context_mut(&mut self) -> &mut DisplayContext {
+        unsafe {
+            let mut context = lv_display_get_user_data(self.raw_mut()).cast::<DisplayContext>();
+            if context.is_null() {
+                context = Box::into_raw(Box::<DisplayContext>::default());
+                lightvgl_sys::lv_display_set_user_data(self.raw_mut(), context.cast());
+                self.drop_with_display(context);
+            }
+            &mut *context
+        }
+    }
+
+    /// Frees `data` with `Box::from_raw()` when the display is deleted
+    unsafe fn drop_with_display<T>(&mut self, data: *mut T) {
+        unsafe {
+            lightvgl_sys::lv_display_add_event_cb(
+                self.raw_mut(),
+                Some(drop_user_data::<T>),
+                lightvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+                data.cast(),
+            );
+        }
+    }
+}
+
+impl Drop for Display {
+    fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+        unsafe {
+            crate::info!("Dropping Display");
+            let raw = self.raw.as_ptr();
+            if lightvgl_sys::lv_display_get_default() == raw {
+                let mut next = lightvgl_sys::lv_display_get_next(::core::ptr::null_mut());
+                while next == raw {
+                    next = lightvgl_sys::lv_display_get_next(next);
+                }
+                lightvgl_sys::lv_display_set_default(next);
+            }
+            // the widgets dropped together with the display have only queued their deletion,
+            // their objects are deleted right away with the screens of the display instead
+            for_each_obj(raw, |obj| {
+                lightvgl_sys::lv_async_call_cancel(Some(crate::widgets::delete_obj), obj.cast());
+            });
+            lightvgl_sys::lv_display_delete(raw);
+        }
+    }
+}
+
+/// Calls `f` with every object on `display`, or on every display if it is null,
+/// including the layers
+pub(crate) unsafe fn for_each_obj<F: FnMut(*mut lv_obj_t)>(display: *mut lv_display_t, f: F) {
+    unsafe extern "C" fn visit<F: FnMut(*mut lv_obj_t)>(
+        obj: *mut lv_obj_t,
+        user_data: *mut c_void,
+    ) -> lightvgl_sys::lv_obj_tree_walk_res_t {
+        unsafe {
+            let (display, f) = &mut *user_data.cast::<(*mut lv_display_t, F)>();
+            if !display.is_null()
+                && lightvgl_sys::lv_obj_get_parent(obj).is_null()
+                && lightvgl_sys::lv_obj_get_display(obj) != *display
+            {
+                return lightvgl_sys::lv_obj_tree_walk_res_t_LV_OBJ_TREE_WALK_SKIP_CHILDREN;
+            }
+            f(obj);
+        }
+        lightvgl_sys::lv_obj_tree_walk_res_t_LV_OBJ_TREE_WALK_NEXT
+    }
+
+    let mut state = (display, f);
+    let user_data: *mut c_void = (&raw mut state).cast();
+    unsafe {
+        // a null start object walks the screens of every display
+        lightvgl_sys::lv_obj_tree_walk(::core::ptr::null_mut(), Some(visit::<F>), user_data);
+        // the layers are walked unfiltered, they belong to the display they are taken from
+        state.0 = ::core::ptr::null_mut();
+        let user_data: *mut c_void = (&raw mut state).cast();
+        let mut next = if display.is_null() {
+            lightvgl_sys::lv_display_get_next(::core::ptr::null_mut())
+        } else {
+            display
+        };
+        while !next.is_null() {
+            for layer in [
+                lightvgl_sys::lv_display_get_layer_bottom(next),
+                lightvgl_sys::lv_display_get_layer_top(next),
+                lightvgl_sys::lv_display_get_layer_sys(next),
+            ] {
+                if !layer.is_null() {
+                    lightvgl_sys::lv_obj_tree_walk(layer, Some(visit::<F>), user_data);
+                }
+            }
+            next = if display.is_null() {
+                lightvgl_sys::lv_display_get_next(next)
+            } else {
+                ::core::ptr::null_mut()
+            };
+        }
+    }
+}
+
+unsafe extern "C" fn drop_user_data<T>(event: *mut lightvgl_sys::lv_event_t) {
+    unsafe {
+        let user_data = lightvgl_sys::lv_event_get_user_data(event);
+        if !user_data.is_null() {
+            drop(Box::from_raw(user_data.cast::<T>()));
+        }
+    }
+}
+
+unsafe extern "C" fn destroy_draw_buf(event: *mut lightvgl_sys::lv_event_t) {
+    unsafe {
+        let draw_buf = lightvgl_sys::lv_event_get_user_data(event);
+        if !draw_buf.is_null() {
+            lightvgl_sys::lv_draw_buf_destroy(draw_buf.cast());
+        }
+    }
 }
 
 fn verify_color_format(cf: lv_color_format_t) {
@@ -272,6 +914,18 @@ fn verify_color_format(cf: lv_color_format_t) {
                 "LV_DRAW_SW_SUPPORT_RGB565 must be set to 1"
             );
         }
+        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565_SWAPPED => {
+            assert_eq!(
+                lightvgl_sys::LV_COLOR_DEPTH,
+                16,
+                "LV_COLOR_DEPTH must be set to 16"
+            );
+            assert_eq!(
+                lightvgl_sys::LV_DRAW_SW_SUPPORT_RGB565_SWAPPED,
+                1,
+                "LV_DRAW_SW_SUPPORT_RGB565_SWAPPED must be set to 1"
+            );
+        }
         lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB888 => {
             assert_eq!(
                 lightvgl_sys::LV_COLOR_DEPTH,
@@ -284,11 +938,48 @@ fn verify_color_format(cf: lv_color_format_t) {
                 "LV_DRAW_SW_SUPPORT_RGB888 must be set to 1"
             );
         }
+        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_ARGB8888 => {
+            assert_eq!(
+                lightvgl_sys::LV_COLOR_DEPTH,
+                32,
+                "LV_COLOR_DEPTH must be set to 32"
+            );
+            assert_eq!(
+                lightvgl_sys::LV_DRAW_SW_SUPPORT_ARGB8888,
+                1,
+                "LV_DRAW_SW_SUPPORT_ARGB8888 must be set to 1"
+            );
+        }
+        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_XRGB8888 => {
+            assert_eq!(
+                lightvgl_sys::LV_COLOR_DEPTH,
+                32,
+                "LV_COLOR_DEPTH must be set to 32"
+            );
+            assert_eq!(
+                lightvgl_sys::LV_DRAW_SW_SUPPORT_XRGB8888,
+                1,
+                "LV_DRAW_SW_SUPPORT_XRGB8888 must be set to 1"
+            );
+        }
         _ => unreachable!("unsupported color format"),
     }
 }
 
+fn verify_buffer_size(render_mode: RenderMode, buffer_size: usize, screen_size: usize) {
+    match render_mode {
+        RenderMode::Partial => {}
+        RenderMode::Direct | RenderMode::Full => {
+            assert!(
+                buffer_size >= screen_size,
+                "{render_mode:?} render mode needs screen-sized buffers ({buffer_size} < {screen_size})"
+            );
+        }
+    }
+}
+
 /// Represents a sub-area of the display that is being updated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Area {
     pub x1: i16,
     pub x2: i16,
@@ -296,63 +987,398 @@ pub struct Area {
     pub y2: i16,
 }
 
+impl Area {
+    /// Extends the area so that its left and right edges are aligned to `columns`,
+    /// and its top and bottom edges to `rows`
+    ///
+    /// Values less than 1 are treated as 1.
+    /// Edges that would overflow stop at `i16::MIN` or `i16::MAX`.
+    ///
+    /// ```
+    /// # use lv_bevy_ecs::display::Area;
+    /// let mut area = Area { x1: 4, x2: i16::MAX, y1: -3, y2: 5 };
+    /// area.align(3, 8);
+    /// assert_eq!(area, Area { x1: 3, x2: i16::MAX, y1: -8, y2: 7 });
+    /// ```
+    #[expect(clippy::arithmetic_side_effects)]
+    pub fn align(&mut self, columns: i16, rows: i16) {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+        let right = columns - 1 - self.x2.rem_euclid(columns);
+        let bottom = rows - 1 - self.y2.rem_euclid(rows);
+        self.x1 = self.x1.saturating_sub(self.x1.rem_euclid(columns));
+        self.x2 = self.x2.saturating_add(right);
+        self.y1 = self.y1.saturating_sub(self.y1.rem_euclid(rows));
+        self.y2 = self.y2.saturating_add(bottom);
+    }
+
+    /// Aligns the rows to the 8-pixel pages of monochrome controllers (e.g. SSD1306)
+    #[inline]
+    pub fn align_to_pages(&mut self) {
+        self.align(1, 8);
+    }
+
+    /// Makes the coordinates start on even and end on odd pixels, as required by some TFT controllers
+    #[inline]
+    pub fn align_to_even(&mut self) {
+        self.align(2, 2);
+    }
+}
+
+/// Coordinates outside of the `i16` range are clamped
+impl From<lv_area_t> for Area {
+    #[inline]
+    fn from(value: lv_area_t) -> Self {
+        fn clamp(coord: i32) -> i16 {
+            i16::try_from(coord).unwrap_or(if coord < 0 { i16::MIN } else { i16::MAX })
+        }
+        Self {
+            x1: clamp(value.x1),
+            x2: clamp(value.x2),
+            y1: clamp(value.y1),
+            y2: clamp(value.y2),
+        }
+    }
+}
+
+impl From<Area> for lv_area_t {
+    #[inline]
+    fn from(value: Area) -> Self {
+        Self {
+            x1: value.x1.into(),
+            y1: value.y1.into(),
+            x2: value.x2.into(),
+            y2: value.y2.into(),
+        }
+    }
+}
+
 /// An update to the display information, contains the area that is being
 /// updated and the color of the pixels that need to be updated. The colors
 /// are represented in a contiguous array.
+///
+/// Formats with less than 8 bits per pixel (e.g. [`BinaryColor`](embedded_graphics::pixelcolor::BinaryColor))
+/// have an empty `colors` slice, use [`data`](DisplayRefresh::data) or [`iter_colors()`](DisplayRefresh::iter_colors) instead.
 pub struct DisplayRefresh<'a, const N: usize, C> {
     pub rectangle: Rectangle,
+    /// The pixels without the padding of the rows, only valid if `LV_DRAW_BUF_STRIDE_ALIGN` is 1
     pub colors: &'a [C],
+    /// Raw pixel data without the palette of indexed formats, rows are [`stride`](DisplayRefresh::stride) bytes long
+    pub data: &'a [u8],
+    /// Number of bytes in a row of [`data`](DisplayRefresh::data), including the padding
+    pub stride: u32,
     pub display: Display,
 }
 
+/// Rust data of a display, stored in its user data and dropped when the display is deleted
+#[derive(Default)]
+struct DisplayContext {
+    /// [`FlushContext`] or [`AsyncFlushContext`] of the registered callback
+    flush: Option<Box<dyn Any>>,
+    area_rounder: Option<Box<dyn FnMut(&mut Area)>>,
+    /// Scratch buffer of [`Display::set_software_rotation()`]
+    rotation_buffer: Option<Vec<u8>>,
+    theme: Option<Theme>,
+    /// Themes whose styles may still be used by widgets until the theme is reapplied
+    replaced_themes: Vec<Theme>,
+}
+
+impl DisplayContext {
+    /// Returns the flush context if it is a `T`, and the scratch buffer for software rotation
+    fn flush_mut<T: 'static>(&mut self) -> Option<(&mut T, Option<&mut Vec<u8>>)> {
+        let flush = self.flush.as_mut()?.downcast_mut::<T>()?;
+        Some((flush, self.rotation_buffer.as_mut()))
+    }
+}
+
+/// Returns the context of a display whose user data was set by [`Display::context_mut()`]
+unsafe fn display_context<'a>(display: *mut lv_display_t) -> Option<&'a mut DisplayContext> {
+    unsafe {
+        lv_display_get_user_data(display)
+            .cast::<DisplayContext>()
+            .as_mut()
+    }
+}
+
+struct FlushContext<F> {
+    callback: F,
+    render_mode: RenderMode,
+}
+
+struct AsyncFlushContext<F, W> {
+    display: NonNull<lv_display_t>,
+    callback: F,
+    wait: W,
+    render_mode: RenderMode,
+    state: Arc<FlushState>,
+}
+
+impl<F, W> Drop for AsyncFlushContext<F, W> {
+    fn drop(&mut self) {
+        // dropped when the display is deleted or the flush callback is replaced,
+        // tokens that are still pending must not touch the display
+        self.state.alive.store(false, Ordering::Release);
+        // LVGL would wait forever for a flush that no token can complete anymore
+        if self.state.flushing.swap(false, Ordering::AcqRel) {
+            unsafe {
+                lv_display_flush_ready(self.display.as_ptr());
+            }
+        }
+    }
+}
+
+/// Shared between the flush context of a display and its [`FlushToken`]s
+struct FlushState {
+    flushing: AtomicBool,
+    alive: AtomicBool,
+}
+
+/// Marks an ongoing flush of a display registered with [`Display::register_async()`]
+///
+/// LVGL is notified that the flush has finished when the token is completed or dropped,
+/// so it can be moved into an interrupt handler or an async task. A token that outlives
+/// its display or flush callback does nothing, the pending flush is finished when the
+/// callback is dropped.
+pub struct FlushToken {
+    display: NonNull<lv_display_t>,
+    state: Arc<FlushState>,
+}
+
+// the display is only accessed after checking that it is still alive
+unsafe impl Send for FlushToken {}
+
+impl FlushToken {
+    /// Tells LVGL that the pixels have been pushed to the screen
+    #[inline]
+    pub fn complete(self) {
+        drop(self);
+    }
+}
+
+impl Drop for FlushToken {
+    fn drop(&mut self) {
+        if self.state.alive.load(Ordering::Acquire)
+            && self.state.flushing.swap(false, Ordering::AcqRel)
+        {
+            unsafe {
+                lv_display_flush_ready(self.display.as_ptr());
+            }
+        }
+    }
+}
+
 #[expect(clippy::arithmetic_side_effects)]
-unsafe extern "C" fn disp_flush_trampoline<F, const N: usize, C>(
-    display: *mut lightvgl_sys::lv_display_t,
-    area: *const lightvgl_sys::lv_area_t,
+unsafe fn refresh_from_raw<'a, const N: usize, C: LvglColorFormat>(
+    display: *mut lv_display_t,
+    area: *const lv_area_t,
+    color_p: *mut u8,
+    render_mode: RenderMode,
+    rotation_buffer: Option<&mut Vec<u8>>,
+) -> DisplayRefresh<'a, N, C> {
+    unsafe {
+        let cf = C::as_lv_color_format_t();
+        // indexed formats start with the palette
+        let buf = color_p.add(C::PALETTE_SIZE);
+
+        // in direct mode `color_p` points to the start of the screen-sized buffer
+        let area = match render_mode {
+            RenderMode::Direct => lv_area_t {
+                x1: 0,
+                y1: 0,
+                x2: lightvgl_sys::lv_display_get_horizontal_resolution(display) - 1,
+                y2: lightvgl_sys::lv_display_get_vertical_resolution(display) - 1,
+            },
+            RenderMode::Partial | RenderMode::Full => *area,
+        };
+        let (area, buf) = match rotation_buffer {
+            Some(scratch) => rotate_in_software(display, area, buf, cf, scratch),
+            None => (area, buf),
+        };
+
+        let w = (area.x2 - area.x1 + 1).cast_unsigned();
+        let h = (area.y2 - area.y1 + 1).cast_unsigned();
+        let rectangle = Rectangle {
+            size: Size {
+                width: w,
+                height: h,
+            },
+            top_left: Point {
+                x: area.x1,
+                y: area.y1,
+            },
+        };
+
+        let stride = lightvgl_sys::lv_draw_buf_width_to_stride(w, cf);
+        let data = ::core::slice::from_raw_parts(buf.cast_const(), (stride * h) as usize);
+        let colors: &[C] = if lightvgl_sys::lv_color_format_get_bpp(cf) < 8 {
+            &[]
+        } else {
+            ::core::slice::from_raw_parts(buf.cast::<C>().cast_const(), (w * h) as usize)
+        };
+
+        DisplayRefresh {
+            rectangle,
+            colors,
+            data,
+            stride,
+            display: Display::from_ptr_unchecked(display),
+        }
+    }
+}
+
+/// Rotates the pixels into `scratch`, the buffer set up by [`Display::set_software_rotation()`]
+///
+/// Returns the area in the coordinates of the panel and the pixels to flush.
+#[expect(clippy::arithmetic_side_effects)]
+unsafe fn rotate_in_software(
+    display: *mut lv_display_t,
+    mut area: lv_area_t,
+    buf: *mut u8,
+    cf: lv_color_format_t,
+    scratch: &mut Vec<u8>,
+) -> (lv_area_t, *mut u8) {
+    unsafe {
+        let rotation = lightvgl_sys::lv_display_get_rotation(display);
+        if rotation == lightvgl_sys::lv_display_rotation_t_LV_DISPLAY_ROTATION_0
+            || lightvgl_sys::lv_color_format_get_bpp(cf) < 8
+        {
+            return (area, buf);
+        }
+
+        let src_w = area.x2 - area.x1 + 1;
+        let src_h = area.y2 - area.y1 + 1;
+        let src_stride = lightvgl_sys::lv_draw_buf_width_to_stride(src_w.cast_unsigned(), cf);
+
+        lightvgl_sys::lv_display_rotate_area(display, &mut area);
+        let dest_w = area.x2 - area.x1 + 1;
+        let dest_h = area.y2 - area.y1 + 1;
+        let dest_stride = lightvgl_sys::lv_draw_buf_width_to_stride(dest_w.cast_unsigned(), cf);
+
+        scratch.resize((dest_stride * dest_h.cast_unsigned()) as usize, 0);
+        lightvgl_sys::lv_draw_sw_rotate(
+            buf.cast_const().cast(),
+            scratch.as_mut_ptr().cast(),
+            src_w,
+            src_h,
+            src_stride.cast_signed(),
+            dest_stride.cast_signed(),
+            rotation,
+            cf,
+        );
+        (area, scratch.as_mut_ptr())
+    }
+}
+
+unsafe extern "C" fn disp_flush_trampoline<F, const N: usize, C: LvglColorFormat>(
+    display: *mut lv_display_t,
+    area: *const lv_area_t,
     color_p: *mut u8,
 ) where
-    F: FnMut(&mut DisplayRefresh<N, C>),
+    F: FnMut(&mut DisplayRefresh<N, C>) + 'static,
 {
     unsafe {
-        let user_data = lv_display_get_user_data(display);
-        if !user_data.is_null() {
-            let callback = &mut *(user_data.cast::<F>());
-
-            let buf = color_p.cast::<C>();
-
-            let w = ((*area).x2 - (*area).x1 + 1).cast_unsigned();
-            let h = ((*area).y2 - (*area).y1 + 1).cast_unsigned();
-            let rectangle = Rectangle {
-                size: Size {
-                    width: w,
-                    height: h,
-                },
-                top_left: Point {
-                    x: (*area).x1,
-                    y: (*area).y1,
-                },
-            };
+        let context =
+            display_context(display).and_then(DisplayContext::flush_mut::<FlushContext<F>>);
+        if let Some((context, scratch)) = context {
+            let mut update = refresh_from_raw(display, area, color_p, context.render_mode, scratch);
+            (context.callback)(&mut update);
+            lv_display_flush_ready(display);
+        } else {
+            crate::warn!("Display flush context was missing, this should never happen!");
+        }
+    }
+}
 
-            let slice = ::core::slice::from_raw_parts(buf, (w * h) as usize);
+unsafe extern "C" fn disp_flush_async_trampoline<F, W, const N: usize, C: LvglColorFormat>(
+    display: *mut lv_display_t,
+    area: *const lv_area_t,
+    color_p: *mut u8,
+) where
+    F: FnMut(&mut DisplayRefresh<N, C>, FlushToken) + 'static,
+    W: 'static,
+{
+    unsafe {
+        let context =
+            display_context(display).and_then(DisplayContext::flush_mut::<AsyncFlushContext<F, W>>);
+        if let Some((context, scratch)) = context {
+            context.state.flushing.store(true, Ordering::Release);
+            let token = FlushToken {
+                display: NonNull::new_unchecked(display),
+                state: Arc::clone(&context.state),
+            };
+            let mut update = refresh_from_raw(display, area, color_p, context.render_mode, scratch);
+            (context.callback)(&mut update, token);
+        } else {
+            crate::warn!("Display flush context was missing, this should never happen!");
+        }
+    }
+}
 
-            let mut update = DisplayRefresh {
-                rectangle,
-                colors: slice,
-                display: Display::from_ptr_unchecked(display),
+#[expect(clippy::arithmetic_side_effects)]
+unsafe extern "C" fn area_rounder_trampoline(event: *mut lightvgl_sys::lv_event_t) {
+    unsafe {
+        let context = lightvgl_sys::lv_event_get_user_data(event).cast::<DisplayContext>();
+        let callback = context
+            .as_mut()
+            .and_then(|context| context.area_rounder.as_mut());
+        let display = lightvgl_sys::lv_event_get_target(event).cast::<lv_display_t>();
+        let area = lightvgl_sys::lv_event_get_param(event).cast::<lv_area_t>();
+        if let Some(callback) = callback
+            && !area.is_null()
+        {
+            let mut rounded = Area::from(*area);
+            callback(&mut rounded);
+            // the flushed area must stay within the panel and the draw buffer
+            let rounded = lv_area_t::from(rounded);
+            *area = lv_area_t {
+                x1: rounded.x1.max(0),
+                y1: rounded.y1.max(0),
+                x2: rounded
+                    .x2
+                    .min(lightvgl_sys::lv_display_get_horizontal_resolution(display) - 1),
+                y2: rounded
+                    .y2
+                    .min(lightvgl_sys::lv_display_get_vertical_resolution(display) - 1),
             };
-            callback(&mut update);
-            lv_display_flush_ready(display);
         } else {
-            crate::warn!("Display callback user data was null, this should never happen!");
+            crate::warn!("Area rounder user data was null, this should never happen!");
         }
     }
 }
 
-impl<const N: usize, C> DisplayRefresh<'_, N, C> {
+unsafe extern "C" fn flush_wait_trampoline<F, W>(display: *mut lv_display_t)
+where
+    F: 'static,
+    W: FnMut() + 'static,
+{
+    unsafe {
+        let context =
+            display_context(display).and_then(DisplayContext::flush_mut::<AsyncFlushContext<F, W>>);
+        if let Some((context, _)) = context {
+            while context.state.flushing.load(Ordering::Acquire) {
+                (context.wait)();
+            }
+        } else {
+            crate::warn!("Display flush context was missing, this should never happen!");
+        }
+    }
+}
+
+impl<const N: usize, C: LvglColorFormat> DisplayRefresh<'_, N, C> {
+    /// Iterates over the colors of the refreshed area in row-major order, decoding packed formats
+    #[inline]
+    pub fn iter_colors(&self) -> impl Iterator<Item = C> + '_ {
+        C::iter_colors(
+            self.colors,
+            self.data,
+            self.rectangle.size.width,
+            self.stride,
+        )
+    }
+
     #[expect(clippy::arithmetic_side_effects)]
     pub fn as_pixels<PC>(&self) -> impl IntoIterator<Item = Pixel<PC>>
     where
-        C: Clone,
         PC: PixelColor + From<C>,
     {
         let area = &self.rectangle;
@@ -363,14 +1389,14 @@ impl<const N: usize, C> DisplayRefresh<'_, N, C> {
 
         let mut ix = x1;
         let mut iy = y1;
-        self.colors.iter().map(move |raw_color| {
+        self.iter_colors().map(move |raw_color| {
             if ix > x2 {
                 ix = x1;
                 iy += 1;
             }
             let point = Point::new(ix, iy);
             ix += 1;
-            Pixel(point, raw_color.to_owned().into())
+            Pixel(point, raw_color.into())
         })
     }
 }
@@ -422,37 +1448,190 @@ impl<const N: usize, C: LvglColorFormat> DrawBuffer<N, C> {
     }
 }
 
+/// A display without a screen that renders into an in-memory framebuffer
+///
+/// Useful for tests and CI machines without a display server. Every flushed area is
+/// copied into the framebuffer and recorded in the dirty-region log.
+pub struct HeadlessDisplay<C: LvglColorFormat> {
+    display: Display,
+    framebuffer: Rc<RefCell<Framebuffer<C>>>,
+}
+
+impl<C: LvglColorFormat + Default> HeadlessDisplay<C> {
+    /// Creates a headless display, the framebuffer is initialized with `C::default()`
+    /// ## Arguments
+    ///  - `hor_res` - Horizontal resolution in pixels
+    ///  - `ver_res` - Vertical resolution in pixels
+    ///  - `buffer` - [DrawBuffer] object that LVGL renders into before flushing
+    pub fn new<const N: usize>(hor_res: usize, ver_res: usize, buffer: DrawBuffer<N, C>) -> Self {
+        let size = Size::new(hor_res.try_into().unwrap(), ver_res.try_into().unwrap());
+        let framebuffer = Rc::new(RefCell::new(Framebuffer {
+            size,
+            pixels: vec![C::default(); hor_res.checked_mul(ver_res).unwrap()],
+            dirty_regions: Vec::new(),
+        }));
+        let display = Display::from_draw_target(FramebufferTarget(Rc::clone(&framebuffer)), buffer);
+        Self {
+            display,
+            framebuffer,
+        }
+    }
+}
+
+impl<C: LvglColorFormat> HeadlessDisplay<C> {
+    /// Returns the size of the framebuffer
+    #[inline]
+    pub fn size(&self) -> Size {
+        self.framebuffer.borrow().size
+    }
+
+    /// Returns the color of the pixel at the given position, or `None` if it is out of bounds
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<C> {
+        let framebuffer = self.framebuffer.borrow();
+        framebuffer
+            .index(x, y)
+            .and_then(|index| framebuffer.pixels.get(index).copied())
+    }
+
+    /// Returns the pixels of the framebuffer in row-major order
+    #[inline]
+    pub fn pixels(&self) -> Ref<'_, [C]> {
+        Ref::map(self.framebuffer.borrow(), |framebuffer| {
+            framebuffer.pixels.as_slice()
+        })
+    }
+
+    /// Returns the memory of the framebuffer, `size_of::<C>()` bytes per pixel in row-major order
+    pub fn as_bytes(&self) -> Ref<'_, [u8]> {
+        Ref::map(self.framebuffer.borrow(), |framebuffer| {
+            let pixels = framebuffer.pixels.as_slice();
+            // `LvglColorFormat` requires pixel types without padding
+            unsafe {
+                ::core::slice::from_raw_parts(
+                    pixels.as_ptr().cast::<u8>(),
+                    ::core::mem::size_of_val(pixels),
+                )
+            }
+        })
+    }
+
+    /// Returns the areas flushed since the display was created or the log was last taken
+    #[inline]
+    pub fn dirty_regions(&self) -> Vec<Rectangle> {
+        self.framebuffer.borrow().dirty_regions.clone()
+    }
+
+    /// Empties the dirty-region log and returns its contents
+    #[inline]
+    pub fn take_dirty_regions(&mut self) -> Vec<Rectangle> {
+        ::core::mem::take(&mut self.framebuffer.borrow_mut().dirty_regions)
+    }
+
+    /// Renders the invalidated areas immediately, without waiting for `lv_timer_handler()`
+    #[inline]
+    pub fn refresh(&mut self) {
+        unsafe {
+            lightvgl_sys::lv_refr_now(self.display.raw_mut());
+        }
+    }
+}
+
+impl<C: LvglColorFormat> Deref for HeadlessDisplay<C> {
+    type Target = Display;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.display
+    }
+}
+
+impl<C: LvglColorFormat> DerefMut for HeadlessDisplay<C> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.display
+    }
+}
+
+struct Framebuffer<C> {
+    size: Size,
+    pixels: Vec<C>,
+    dirty_regions: Vec<Rectangle>,
+}
+
+impl<C> Framebuffer<C> {
+    #[expect(clippy::arithmetic_side_effects)]
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.size.width && y < self.size.height).then(|| (y * self.size.width + x) as usize)
+    }
+
+    fn set_pixel(&mut self, point: Point, color: C) {
+        let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) else {
+            return;
+        };
+        if let Some(pixel) = self
+            .index(x, y)
+            .and_then(|index| self.pixels.get_mut(index))
+        {
+            *pixel = color;
+        }
+    }
+}
+
+struct FramebufferTarget<C>(Rc<RefCell<Framebuffer<C>>>);
+
+impl<C> OriginDimensions for FramebufferTarget<C> {
+    #[inline]
+    fn size(&self) -> Size {
+        self.0.borrow().size
+    }
+}
+
+impl<C: LvglColorFormat> DrawTarget for FramebufferTarget<C> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut framebuffer = self.0.borrow_mut();
+        for Pixel(point, color) in pixels {
+            framebuffer.set_pixel(point, color);
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let mut framebuffer = self.0.borrow_mut();
+        framebuffer.dirty_regions.push(*area);
+        for (point, color) in area.points().zip(colors) {
+            framebuffer.set_pixel(point, color);
+        }
+        Ok(())
+    }
+}
+
 /// Using a macro because #\[cfg(doctest)\] does not work as expected
-/// and this snippet requires embedded_graphics_simulator
 ///
 /// <https://github.com/rust-lang/rust/issues/67295>
 #[macro_export]
 macro_rules! setup_test_display {
     () => {
-        use embedded_graphics::draw_target::DrawTarget;
         use embedded_graphics::pixelcolor::Rgb565;
-        use embedded_graphics::prelude::{Point, Size};
-        use embedded_graphics_simulator::SimulatorDisplay;
-        use lv_bevy_ecs::display::{Display, DrawBuffer};
+        use lv_bevy_ecs::display::{DrawBuffer, HeadlessDisplay};
 
         const HOR_RES: usize = 320;
         const VER_RES: usize = 240;
         const LINE_HEIGHT: usize = 16;
 
-        let mut sim_display: SimulatorDisplay<Rgb565> =
-            SimulatorDisplay::new(Size::new(HOR_RES as u32, VER_RES as u32));
-
         lv_bevy_ecs::functions::lv_init();
 
-        let mut display = Display::new(HOR_RES, VER_RES);
-
         let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
 
-        display.register(buffer, move |refresh| {
-            //sim_display.draw_iter(refresh.as_pixels()).unwrap();
-            sim_display
-                .fill_contiguous(&refresh.rectangle, refresh.colors.iter().cloned())
-                .unwrap();
-        });
+        let _display = HeadlessDisplay::new(HOR_RES, VER_RES, buffer);
     };
 }
@@ -0,0 +1,234 @@
+//! # Flex layout
+//!
+//! A [`FlexLayout`] component arranges the children of a container entity in rows or columns,
+//! a [`FlexItem`] component makes a child grow into the free space. Like the style property
+//! components, they are applied when inserted, removed with the component and changes are
+//! applied by [`sync_style_properties`](crate::styles::sync_style_properties).
+//!
+//! ```
+//! # use lv_bevy_ecs::flex::{FlexAlign, FlexFlow, FlexItem, FlexLayout};
+//! # use lv_bevy_ecs::styles::{Part, sync_style_properties};
+//! # use lv_bevy_ecs::support::Coord;
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! # let mut world = LvglWorld::default();
+//! #
+//! let toolbar = world
+//!     .spawn((
+//!         Widget::new(),
+//!         FlexLayout::new(FlexFlow::Row)
+//!             .with_main_place(FlexAlign::SpaceBetween)
+//!             .with_gap(8, 8),
+//!     ))
+//!     .with_children(|parent| {
+//!         parent.spawn(Button::new().into_inner());
+//!         parent.spawn((Label::new().into_inner(), FlexItem { grow: 1 }));
+//!         parent.spawn(Button::new().into_inner());
+//!     })
+//!     .id();
+//!
+//! let mut layout = world.get_mut::<FlexLayout>(toolbar).unwrap();
+//! layout.flow = FlexFlow::Column;
+//! layout.column_gap = Some(Coord::Px(4));
+//! world.run_system_cached(sync_style_properties).unwrap();
+//!
+//! let widget = world.get::<Widget>(toolbar).unwrap();
+//! assert_eq!(widget.get_style_flex_flow(Part::Main), FlexFlow::Column.into());
+//! assert_eq!(widget.get_style_pad_row(Part::Main), 8);
+//! assert_eq!(widget.get_style_pad_column(Part::Main), 4);
+//! ```
+
+use bevy_ecs::component::Component;
+use lightvgl_sys::{lv_flex_align_t, lv_flex_flow_t, lv_obj_t, lv_style_prop_t};
+
+use crate::{
+    styles::{Selector, StyleProperty, apply_property, remove_property},
+    support::Coord,
+};
+
+/// Direction of a flex layout, the items wrap into new tracks with the `*Wrap` variants
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum FlexFlow {
+    #[default]
+    Row = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_ROW,
+    Column = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_COLUMN,
+    RowWrap = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_ROW_WRAP,
+    RowReverse = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_ROW_REVERSE,
+    RowWrapReverse = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_ROW_WRAP_REVERSE,
+    ColumnWrap = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_COLUMN_WRAP,
+    ColumnReverse = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_COLUMN_REVERSE,
+    ColumnWrapReverse = lightvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_COLUMN_WRAP_REVERSE,
+}
+
+impl From<FlexFlow> for lv_flex_flow_t {
+    #[inline]
+    fn from(value: FlexFlow) -> Self {
+        value as Self
+    }
+}
+
+/// Placement of the items or the tracks along an axis
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum FlexAlign {
+    #[default]
+    Start = lightvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_START,
+    End = lightvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_END,
+    Center = lightvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_CENTER,
+    SpaceEvenly = lightvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_SPACE_EVENLY,
+    SpaceAround = lightvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_SPACE_AROUND,
+    SpaceBetween = lightvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_SPACE_BETWEEN,
+}
+
+impl From<FlexAlign> for lv_flex_align_t {
+    #[inline]
+    fn from(value: FlexAlign) -> Self {
+        value as Self
+    }
+}
+
+/// Flex layout of a container's children
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[component(on_insert = apply_property::<FlexLayout>)]
+#[component(on_discard = remove_property::<FlexLayout>)]
+pub struct FlexLayout {
+    pub flow: FlexFlow,
+    /// Placement of the items along the flow direction
+    pub main_place: FlexAlign,
+    /// Placement of the items in their track
+    pub cross_place: FlexAlign,
+    /// Placement of the tracks when wrapping
+    pub track_place: FlexAlign,
+    /// Space between the rows, `None` keeps the value of the theme
+    pub row_gap: Option<Coord>,
+    /// Space between the columns, `None` keeps the value of the theme
+    pub column_gap: Option<Coord>,
+}
+
+impl FlexLayout {
+    #[inline]
+    pub fn new(flow: FlexFlow) -> Self {
+        Self {
+            flow,
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    pub fn with_main_place(mut self, place: FlexAlign) -> Self {
+        self.main_place = place;
+        self
+    }
+
+    #[inline]
+    pub fn with_cross_place(mut self, place: FlexAlign) -> Self {
+        self.cross_place = place;
+        self
+    }
+
+    #[inline]
+    pub fn with_track_place(mut self, place: FlexAlign) -> Self {
+        self.track_place = place;
+        self
+    }
+
+    #[inline]
+    pub fn with_gap(mut self, row: impl Into<Coord>, column: impl Into<Coord>) -> Self {
+        self.row_gap = Some(row.into());
+        self.column_gap = Some(column.into());
+        self
+    }
+}
+
+impl StyleProperty for FlexLayout {
+    fn apply(&mut self, obj: *mut lv_obj_t) {
+        unsafe {
+            lightvgl_sys::lv_obj_set_flex_flow(obj, self.flow.into());
+            lightvgl_sys::lv_obj_set_flex_align(
+                obj,
+                self.main_place.into(),
+                self.cross_place.into(),
+                self.track_place.into(),
+            );
+            let selector = Selector::default().bits();
+            match self.row_gap {
+                Some(gap) => lightvgl_sys::lv_obj_set_style_pad_row(obj, gap.into(), selector),
+                None => {
+                    lightvgl_sys::lv_obj_remove_local_style_prop(
+                        obj,
+                        lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_ROW as lv_style_prop_t,
+                        selector,
+                    );
+                }
+            }
+            match self.column_gap {
+                Some(gap) => lightvgl_sys::lv_obj_set_style_pad_column(obj, gap.into(), selector),
+                None => {
+                    lightvgl_sys::lv_obj_remove_local_style_prop(
+                        obj,
+                        lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_COLUMN as lv_style_prop_t,
+                        selector,
+                    );
+                }
+            }
+        }
+    }
+
+    fn remove(&self, obj: *mut lv_obj_t) {
+        let gaps = [
+            self.row_gap
+                .map(|_| lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_ROW),
+            self.column_gap
+                .map(|_| lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_COLUMN),
+        ];
+        let props = [
+            lightvgl_sys::_lv_style_id_t_LV_STYLE_LAYOUT,
+            lightvgl_sys::_lv_style_id_t_LV_STYLE_FLEX_FLOW,
+            lightvgl_sys::_lv_style_id_t_LV_STYLE_FLEX_MAIN_PLACE,
+            lightvgl_sys::_lv_style_id_t_LV_STYLE_FLEX_CROSS_PLACE,
+            lightvgl_sys::_lv_style_id_t_LV_STYLE_FLEX_TRACK_PLACE,
+        ];
+        for prop in props.into_iter().chain(gaps.into_iter().flatten()) {
+            unsafe {
+                lightvgl_sys::lv_obj_remove_local_style_prop(
+                    obj,
+                    prop as lv_style_prop_t,
+                    Selector::default().bits(),
+                );
+            }
+        }
+    }
+}
+
+/// Makes a child of a [`FlexLayout`] grow into the free space
+///
+/// The free space is shared between the growing items in proportion to `grow`.
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[component(on_insert = apply_property::<FlexItem>)]
+#[component(on_discard = remove_property::<FlexItem>)]
+pub struct FlexItem {
+    pub grow: u8,
+}
+
+impl StyleProperty for FlexItem {
+    fn apply(&mut self, obj: *mut lv_obj_t) {
+        unsafe {
+            lightvgl_sys::lv_obj_set_flex_grow(obj, self.grow);
+        }
+    }
+
+    fn remove(&self, obj: *mut lv_obj_t) {
+        unsafe {
+            lightvgl_sys::lv_obj_remove_local_style_prop(
+                obj,
+                lightvgl_sys::_lv_style_id_t_LV_STYLE_FLEX_GROW as lv_style_prop_t,
+                Selector::default().bits(),
+            );
+        }
+    }
+}
@@ -110,4 +110,22 @@ pub fn lv_screen_active() -> Option<Wdg> {
     unsafe { Wdg::try_from_ptr(lightvgl_sys::lv_screen_active()) }
 }
 
+/// Layer above every screen of the default display, e.g. for dialogs and notifications
+#[inline]
+pub fn lv_layer_top() -> Option<Wdg> {
+    unsafe { Wdg::try_from_ptr(lightvgl_sys::lv_layer_top()) }
+}
+
+/// Layer above the top layer, reserved for system objects such as the mouse cursor
+#[inline]
+pub fn lv_layer_sys() -> Option<Wdg> {
+    unsafe { Wdg::try_from_ptr(lightvgl_sys::lv_layer_sys()) }
+}
+
+/// Layer below every screen of the default display, e.g. for wallpapers
+#[inline]
+pub fn lv_layer_bottom() -> Option<Wdg> {
+    unsafe { Wdg::try_from_ptr(lightvgl_sys::lv_layer_bottom()) }
+}
+
 include!(concat!(env!("OUT_DIR"), "/generated.rs"));
@@ -0,0 +1,216 @@
+//! # Golden images
+//!
+//! Screenshot regression testing on top of [`HeadlessDisplay`]. The screen is rendered,
+//! encoded to PNG with LodePNG (`LV_USE_LODEPNG` must be enabled) and compared against
+//! a reference image. If any pixel differs by more than the tolerance, a diff image with the
+//! mismatched pixels marked red is written next to the reference.
+//!
+//! Run the tests with `LV_BLESS=1` to create or update the references.
+//!
+//! ```no_run
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use lv_bevy_ecs::display::{DrawBuffer, HeadlessDisplay};
+//! # use lv_bevy_ecs::golden::assert_golden;
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! lv_bevy_ecs::functions::lv_init();
+//! const HOR_RES: usize = 320;
+//! const VER_RES: usize = 240;
+//! const LINE_HEIGHT: usize = 16;
+//!
+//! let buffer = DrawBuffer::<{ HOR_RES * LINE_HEIGHT }, Rgb565>::new(HOR_RES, LINE_HEIGHT);
+//! let mut display = HeadlessDisplay::new(HOR_RES, VER_RES, buffer);
+//!
+//! let mut world = LvglWorld::default();
+//! let mut label = Label::new();
+//! label.set_text(c"Hello");
+//! world.spawn(label.into_inner());
+//!
+//! assert_golden(&mut display, "tests/golden/hello.png", 2);
+//! ```
+use ::alloc::vec::Vec;
+use ::core::ffi::c_uint;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::RgbColor};
+use thiserror::Error;
+
+use crate::{
+    display::HeadlessDisplay,
+    png::{decode_png, encode_png},
+    support::LvglColorFormat,
+};
+
+/// Environment variable that makes [`compare_golden()`] overwrite the reference images
+pub const BLESS_ENV: &str = "LV_BLESS";
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GoldenError {
+    #[error("reference image {} does not exist, run with {BLESS_ENV}=1 to create it", .0.display())]
+    Missing(PathBuf),
+    #[error("reference image is {reference:?}, but the display is {actual:?}")]
+    SizeMismatch {
+        reference: (u32, u32),
+        actual: (u32, u32),
+    },
+    #[error("{mismatched} pixels differ from {}, see {}", reference.display(), diff.display())]
+    Mismatch {
+        reference: PathBuf,
+        diff: PathBuf,
+        mismatched: usize,
+    },
+    #[error("LodePNG error {0}")]
+    Png(c_uint),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Renders the screen and compares it against the reference PNG at `reference`
+///
+/// `tolerance` is the maximum allowed difference of each color channel.
+/// If [`BLESS_ENV`] is set to anything but an empty string or `0`, the reference is
+/// overwritten instead.
+///
+/// ```
+/// # use embedded_graphics::pixelcolor::Rgb565;
+/// # use lv_bevy_ecs::display::{DrawBuffer, HeadlessDisplay};
+/// # use lv_bevy_ecs::golden::{compare_golden, GoldenError, BLESS_ENV};
+/// # use lv_bevy_ecs::widgets::*;
+/// #
+/// lv_bevy_ecs::functions::lv_init();
+/// let buffer = DrawBuffer::<{ 64 * 8 }, Rgb565>::new(64, 8);
+/// let mut display = HeadlessDisplay::new(64, 32, buffer);
+/// let reference = std::env::temp_dir()
+///     .join(format!("lv_bevy_ecs_golden_{}", std::process::id()))
+///     .join("label.png");
+///
+/// let mut world = LvglWorld::default();
+/// let mut label = Label::new();
+/// label.set_text(c"Hello");
+/// world.spawn(label.into_inner());
+/// assert!(matches!(
+///     compare_golden(&mut display, &reference, 0),
+///     Err(GoldenError::Missing(_))
+/// ));
+///
+/// // SAFETY: no other thread reads the environment
+/// unsafe { std::env::set_var(BLESS_ENV, "1") };
+/// compare_golden(&mut display, &reference, 0).unwrap();
+/// unsafe { std::env::set_var(BLESS_ENV, "0") };
+/// compare_golden(&mut display, &reference, 0).unwrap();
+///
+/// let mut label = Label::new();
+/// label.set_text(c"World");
+/// world.spawn(label.into_inner());
+/// assert!(matches!(
+///     compare_golden(&mut display, &reference, 0),
+///     Err(GoldenError::Mismatch { diff, .. }) if diff.exists()
+/// ));
+/// # std::fs::remove_dir_all(reference.parent().unwrap()).unwrap();
+/// ```
+pub fn compare_golden<C>(
+    display: &mut HeadlessDisplay<C>,
+    reference: impl AsRef<Path>,
+    tolerance: u8,
+) -> Result<(), GoldenError>
+where
+    C: LvglColorFormat + Into<Rgb888>,
+{
+    display.refresh();
+    let size = display.size();
+    let actual = to_rgba(&display.pixels());
+    let reference = reference.as_ref();
+
+    if bless() {
+        if let Some(parent) = reference.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            reference,
+            encode_png(&actual, size.width, size.height).map_err(GoldenError::Png)?,
+        )?;
+        return Ok(());
+    }
+
+    let png = match fs::read(reference) {
+        Ok(png) => png,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Err(GoldenError::Missing(reference.to_path_buf()));
+        }
+        Err(error) => return Err(error.into()),
+    };
+    let (expected, width, height) = decode_png(&png).map_err(GoldenError::Png)?;
+    if (width, height) != (size.width, size.height) {
+        return Err(GoldenError::SizeMismatch {
+            reference: (width, height),
+            actual: (size.width, size.height),
+        });
+    }
+
+    let mut mismatched = 0;
+    let mut diff = Vec::with_capacity(actual.len());
+    for (actual, expected) in actual.chunks_exact(4).zip(expected.chunks_exact(4)) {
+        if actual
+            .iter()
+            .zip(expected)
+            .any(|(actual, expected)| actual.abs_diff(*expected) > tolerance)
+        {
+            mismatched += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // faded, so the mismatched pixels stand out
+            diff.extend(actual.iter().take(3).map(|channel| channel / 4 + 192));
+            diff.push(255);
+        }
+    }
+    if mismatched == 0 {
+        return Ok(());
+    }
+
+    let diff_path = reference.with_extension("diff.png");
+    fs::write(
+        &diff_path,
+        encode_png(&diff, size.width, size.height).map_err(GoldenError::Png)?,
+    )?;
+    Err(GoldenError::Mismatch {
+        reference: reference.to_path_buf(),
+        diff: diff_path,
+        mismatched,
+    })
+}
+
+/// Same as [`compare_golden()`], but panics on mismatch
+#[track_caller]
+pub fn assert_golden<C>(
+    display: &mut HeadlessDisplay<C>,
+    reference: impl AsRef<Path>,
+    tolerance: u8,
+) where
+    C: LvglColorFormat + Into<Rgb888>,
+{
+    if let Err(error) = compare_golden(display, reference, tolerance) {
+        #[expect(clippy::panic)]
+        {
+            panic!("{error}");
+        }
+    }
+}
+
+/// `LV_BLESS=0` and an empty `LV_BLESS` compare as if it was unset
+fn bless() -> bool {
+    env::var_os(BLESS_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+fn to_rgba<C: LvglColorFormat + Into<Rgb888>>(pixels: &[C]) -> Vec<u8> {
+    pixels
+        .iter()
+        .flat_map(|&color| {
+            let color: Rgb888 = color.into();
+            [color.r(), color.g(), color.b(), 255]
+        })
+        .collect()
+}
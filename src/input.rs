@@ -1,12 +1,38 @@
-//! Input devices
-use ::alloc::boxed::Box;
-use ::core::{marker::PhantomData, ptr::NonNull};
+//! # Input devices
+//!
+//! ## Recording and replay
+//!
+//! Every [`InputEvent`] read by an [`InputDevice`] can be recorded together with the LVGL tick
+//! it was read at and the index of the device that read it. The resulting [`InputRecording`] can
+//! be serialized (with the `serde` feature) and replayed later as virtual input devices, one for
+//! each recorded device.
+//!
+//! ```
+//! # use embedded_graphics::prelude::*;
+//! # use lv_bevy_ecs::input::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! lv_bevy_ecs::input::start_recording();
+//! let touch_screen = InputDevice::<Pointer>::new(|| InputEvent::new(Point::new(10, 20)));
+//! // ... run lv_timer_handler() while the user interacts with the UI
+//! let recording = lv_bevy_ecs::input::stop_recording().unwrap();
+//! // deletes the LVGL input device
+//! drop(touch_screen);
+//!
+//! // later, against a fresh UI
+//! let _replay = InputDevice::<Pointer>::replay(recording, 0);
+//! ```
+use ::alloc::{boxed::Box, vec::Vec};
+use ::core::{ffi::c_void, marker::PhantomData, ptr::NonNull};
+use bevy_platform::sync::{Mutex, PoisonError};
 use lightvgl_sys::{lv_indev_get_user_data, lv_indev_state_t, lv_indev_t, lv_indev_type_t};
 
 use embedded_graphics::prelude::Point;
 
 /// Boolean states for an input.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InputState {
     /// Input device key is currently pressed down.
     Pressed,
@@ -26,6 +52,7 @@ impl InputState {
 
 /// Boolean buffering states for an input device driver.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BufferStatus {
     /// One instance of `InputState` remains to be read.
     #[default]
@@ -56,6 +83,18 @@ pub trait InputType {
     type DataType;
     fn as_lv_indev_type() -> lv_indev_type_t;
     fn set_lv_indev_data(event_data: &Self::DataType, data: &mut lightvgl_sys::lv_indev_data_t);
+    fn to_input_data(event_data: &Self::DataType) -> InputData;
+    fn from_input_data(data: &InputData) -> Option<Self::DataType>;
+}
+
+/// Type-erased payload of an [`InputEvent`], used by [`InputRecording`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputData {
+    Pointer { x: i32, y: i32 },
+    Keypad(u32),
+    Encoder(i16),
+    Button(u32),
 }
 
 #[derive(Clone, Copy, Default)]
@@ -74,6 +113,22 @@ impl InputType for Pointer {
         data.point.x = event_data.x;
         data.point.y = event_data.y;
     }
+
+    #[inline]
+    fn to_input_data(event_data: &Self::DataType) -> InputData {
+        InputData::Pointer {
+            x: event_data.x,
+            y: event_data.y,
+        }
+    }
+
+    #[inline]
+    fn from_input_data(data: &InputData) -> Option<Self::DataType> {
+        match *data {
+            InputData::Pointer { x, y } => Some(Point::new(x, y)),
+            _ => None,
+        }
+    }
 }
 
 pub struct Keypad;
@@ -90,6 +145,19 @@ impl InputType for Keypad {
     fn set_lv_indev_data(event_data: &Self::DataType, data: &mut lightvgl_sys::lv_indev_data_t) {
         data.key = *event_data;
     }
+
+    #[inline]
+    fn to_input_data(event_data: &Self::DataType) -> InputData {
+        InputData::Keypad(*event_data)
+    }
+
+    #[inline]
+    fn from_input_data(data: &InputData) -> Option<Self::DataType> {
+        match *data {
+            InputData::Keypad(value) => Some(value),
+            _ => None,
+        }
+    }
 }
 
 pub struct Encoder;
@@ -106,6 +174,19 @@ impl InputType for Encoder {
     fn set_lv_indev_data(event_data: &Self::DataType, data: &mut lightvgl_sys::lv_indev_data_t) {
         data.enc_diff = *event_data;
     }
+
+    #[inline]
+    fn to_input_data(event_data: &Self::DataType) -> InputData {
+        InputData::Encoder(*event_data)
+    }
+
+    #[inline]
+    fn from_input_data(data: &InputData) -> Option<Self::DataType> {
+        match *data {
+            InputData::Encoder(value) => Some(value),
+            _ => None,
+        }
+    }
 }
 pub struct Button;
 
@@ -121,22 +202,45 @@ impl InputType for Button {
     fn set_lv_indev_data(event_data: &Self::DataType, data: &mut lightvgl_sys::lv_indev_data_t) {
         data.btn_id = *event_data;
     }
+
+    #[inline]
+    fn to_input_data(event_data: &Self::DataType) -> InputData {
+        InputData::Button(*event_data)
+    }
+
+    #[inline]
+    fn from_input_data(data: &InputData) -> Option<Self::DataType> {
+        match *data {
+            InputData::Button(value) => Some(value),
+            _ => None,
+        }
+    }
 }
 
+/// An LVGL input device, deleted together with its read callback when dropped
 pub struct InputDevice<T: InputType> {
     raw: NonNull<lv_indev_t>,
+    /// Frees the boxed read callback in the user data
+    drop_read_cb: unsafe fn(*mut c_void),
     r#type: PhantomData<T>,
 }
 
 impl<T: InputType> InputDevice<T> {
     pub fn new<F>(read_cb: F) -> Self
+    where
+        F: FnMut() -> InputEvent<T>,
+    {
+        Self::with_read_cb::<F, true>(read_cb)
+    }
+
+    fn with_read_cb<F, const RECORD: bool>(read_cb: F) -> Self
     where
         F: FnMut() -> InputEvent<T>,
     {
         unsafe {
             let raw = NonNull::new(lightvgl_sys::lv_indev_create()).unwrap();
             lightvgl_sys::lv_indev_set_type(raw.as_ptr(), T::as_lv_indev_type());
-            lightvgl_sys::lv_indev_set_read_cb(raw.as_ptr(), Some(read_input::<F, T>));
+            lightvgl_sys::lv_indev_set_read_cb(raw.as_ptr(), Some(read_input::<F, T, RECORD>));
             lightvgl_sys::lv_indev_set_user_data(
                 raw.as_ptr(),
                 Box::into_raw(Box::new(read_cb)).cast(),
@@ -144,11 +248,89 @@ impl<T: InputType> InputDevice<T> {
 
             Self {
                 raw,
+                drop_read_cb: drop_box::<F>,
                 r#type: PhantomData,
             }
         }
     }
 
+    /// Creates a virtual input device that plays back the events of type `T` that the device
+    /// with index `device` read during `recording`.
+    ///
+    /// Ticks are counted from the moment this function is called, the same way
+    /// [`start_recording`] counts them. Events read together in one buffered read are played
+    /// back together. Once every event has been played back, the last one is repeated.
+    /// Replay devices are never recorded themselves.
+    ///
+    /// ```
+    /// # use embedded_graphics::prelude::*;
+    /// # use lv_bevy_ecs::input::*;
+    /// # use lv_bevy_ecs::sys::{lv_indev_get_point, lv_indev_read, lv_point_t};
+    /// #
+    /// # lv_bevy_ecs::setup_test_display!();
+    /// #
+    /// lv_bevy_ecs::input::start_recording();
+    /// let mut first = InputDevice::<Pointer>::new(|| InputEvent::new(Point::new(10, 20)));
+    /// let mut second = InputDevice::<Pointer>::new(|| InputEvent::new(Point::new(30, 40)));
+    /// unsafe {
+    ///     lv_indev_read(first.raw_mut());
+    ///     lv_indev_read(second.raw_mut());
+    /// }
+    /// let recording = lv_bevy_ecs::input::stop_recording().unwrap();
+    /// assert_eq!(recording.devices(), 2);
+    ///
+    /// lv_bevy_ecs::input::start_recording();
+    /// let mut replay = InputDevice::<Pointer>::replay(recording, 1);
+    /// let mut point = lv_point_t { x: 0, y: 0 };
+    /// unsafe {
+    ///     lv_indev_read(replay.raw_mut());
+    ///     lv_indev_get_point(replay.raw(), &mut point);
+    /// }
+    /// assert_eq!((point.x, point.y), (30, 40));
+    /// assert!(lv_bevy_ecs::input::stop_recording().unwrap().events.is_empty());
+    /// ```
+    pub fn replay(recording: InputRecording, device: u32) -> Self
+    where
+        T::DataType: Copy + Default,
+    {
+        let events = recording
+            .events
+            .into_iter()
+            .filter(|recorded| recorded.device == device)
+            .filter_map(|recorded| {
+                T::from_input_data(&recorded.data)
+                    .map(|data| (recorded.tick, recorded.status, recorded.state, data))
+            })
+            .collect::<Vec<_>>();
+        let start = unsafe { lightvgl_sys::lv_tick_get() };
+        let mut next = 0;
+        let mut last_state = InputState::default();
+        let mut last_data = T::DataType::default();
+
+        Self::with_read_cb::<_, false>(move || {
+            let elapsed = unsafe { lightvgl_sys::lv_tick_elaps(start) };
+            let mut status = BufferStatus::Once;
+            if let Some((tick, recorded_status, state, data)) = events.get(next)
+                && *tick <= elapsed
+            {
+                last_state = *state;
+                last_data = *data;
+                next += 1;
+                // keep reading only if the rest of the buffered read is due as well
+                if *recorded_status == BufferStatus::Buffered
+                    && events.get(next).is_some_and(|(tick, ..)| *tick <= elapsed)
+                {
+                    status = BufferStatus::Buffered;
+                }
+            }
+            InputEvent {
+                status,
+                state: last_state,
+                data: last_data,
+            }
+        })
+    }
+
     #[inline]
     pub fn raw(&self) -> *const lv_indev_t {
         self.raw.as_ptr().cast_const()
@@ -160,7 +342,24 @@ impl<T: InputType> InputDevice<T> {
     }
 }
 
-unsafe extern "C" fn read_input<F, T>(
+impl<T: InputType> Drop for InputDevice<T> {
+    fn drop(&mut self) {
+        forget_device(self.raw());
+        unsafe {
+            let read_cb = lv_indev_get_user_data(self.raw.as_ptr());
+            lightvgl_sys::lv_indev_delete(self.raw.as_ptr());
+            (self.drop_read_cb)(read_cb);
+        }
+    }
+}
+
+unsafe fn drop_box<F>(data: *mut c_void) {
+    if !data.is_null() {
+        drop(unsafe { Box::from_raw(data.cast::<F>()) });
+    }
+}
+
+unsafe extern "C" fn read_input<F, T, const RECORD: bool>(
     indev: *mut lv_indev_t,
     data: *mut lightvgl_sys::lv_indev_data_t,
 ) where
@@ -182,8 +381,119 @@ unsafe extern "C" fn read_input<F, T>(
             }
             T::set_lv_indev_data(&event.data, data.as_mut().unwrap());
             (*data).state = event.state.as_lv_indev_state();
+            if RECORD {
+                record_event(indev, &event);
+            }
         } else {
             crate::warn!("Input callback user data was null, this should never happen!");
         }
     }
 }
+
+/// An [`InputEvent`] captured while recording, stamped with the LVGL tick it was read at
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedEvent {
+    /// Milliseconds elapsed since [`start_recording`] was called
+    pub tick: u32,
+    /// Index of the device in the order the devices were first read during the recording
+    pub device: u32,
+    pub status: BufferStatus,
+    pub state: InputState,
+    pub data: InputData,
+}
+
+/// Every [`InputEvent`] read between [`start_recording`] and [`stop_recording`]
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputRecording {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+    /// Returns the number of recorded devices
+    pub fn devices(&self) -> u32 {
+        self.events
+            .iter()
+            .map(|event| event.device.saturating_add(1))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+struct Recorder {
+    start: u32,
+    /// Addresses of the recorded devices, deleted devices are set to 0 to keep the indices
+    devices: Vec<usize>,
+    recording: InputRecording,
+}
+
+// lv_indev_read_cb_t has no access to global state other than the user data,
+// which is already taken by the read callback
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+fn with_recorder<R>(f: impl FnOnce(&mut Option<Recorder>) -> R) -> R {
+    f(&mut RECORDER.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Starts recording the events of every [`InputDevice`]. An ongoing recording is discarded.
+pub fn start_recording() {
+    crate::support::assert_lv_is_initialized();
+    let start = unsafe { lightvgl_sys::lv_tick_get() };
+    with_recorder(|recorder| {
+        *recorder = Some(Recorder {
+            start,
+            devices: Vec::new(),
+            recording: InputRecording::default(),
+        });
+    });
+    crate::info!("Input recording started");
+}
+
+/// Stops recording and returns the recorded events, or `None` if no recording was in progress.
+pub fn stop_recording() -> Option<InputRecording> {
+    let recorder = with_recorder(Option::take)?;
+    crate::info!("Input recording stopped");
+    Some(recorder.recording)
+}
+
+fn record_event<T: InputType>(indev: *const lv_indev_t, event: &InputEvent<T>) {
+    let tick = unsafe { lightvgl_sys::lv_tick_get() };
+    with_recorder(|recorder| {
+        let Some(recorder) = recorder.as_mut() else {
+            return;
+        };
+        let address = indev.addr();
+        let device = match recorder
+            .devices
+            .iter()
+            .position(|device| *device == address)
+        {
+            Some(index) => index,
+            None => {
+                recorder.devices.push(address);
+                recorder.devices.len().saturating_sub(1)
+            }
+        };
+        recorder.recording.events.push(RecordedEvent {
+            tick: tick.wrapping_sub(recorder.start),
+            device: device.try_into().unwrap_or(u32::MAX),
+            status: event.status,
+            state: event.state,
+            data: T::to_input_data(&event.data),
+        });
+    });
+}
+
+/// Keeps a new device created at the same address from reusing the index of a deleted one
+fn forget_device(indev: *const lv_indev_t) {
+    with_recorder(|recorder| {
+        if let Some(recorder) = recorder.as_mut() {
+            for device in &mut recorder.devices {
+                if *device == indev.addr() {
+                    *device = 0;
+                }
+            }
+        }
+    });
+}
@@ -136,6 +136,8 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "golden")]
+extern crate std;
 
 pub use bevy_ecs as bevy;
 pub use lightvgl_sys as sys;
@@ -143,20 +145,40 @@ pub use lightvgl_sys as sys;
 #[cfg(feature = "lvgl-alloc")]
 pub mod allocator;
 pub mod animation;
+#[cfg(feature = "app")]
+pub mod app;
+pub mod colors;
 pub mod display;
 pub mod events;
+#[cfg(LV_USE_FLEX)]
+pub mod flex;
 pub mod functions;
+#[cfg(all(feature = "golden", LV_USE_LODEPNG))]
+pub mod golden;
 pub mod input;
 pub mod logging;
 #[cfg(feature = "rust-alloc")]
 pub mod malloc;
+#[cfg(all(LV_USE_LODEPNG, any(LV_USE_SNAPSHOT, feature = "golden")))]
+mod png;
+pub mod screens;
+#[cfg(LV_USE_SNAPSHOT)]
+pub mod snapshot;
+#[cfg(feature = "states")]
+pub mod states;
+#[cfg(feature = "serde")]
+pub mod style_sheet;
 pub mod styles;
 pub mod subjects;
 pub mod support;
+pub mod themes;
 pub mod timers;
 #[macro_use]
 pub mod widgets;
 
+#[cfg(all(feature = "golden", not(LV_USE_LODEPNG)))]
+compile_error!("The `golden` feature requires `LV_USE_LODEPNG` to be enabled in lv_conf.h");
+
 #[cfg(feature = "ctor")]
 #[ctor::ctor]
 unsafe fn init() {
@@ -0,0 +1,110 @@
+//! PNG encoding and decoding with the LodePNG copy bundled with LVGL (`LV_USE_LODEPNG`)
+
+use ::alloc::vec::Vec;
+use ::core::{
+    ffi::c_uint,
+    ptr::{self, NonNull},
+};
+
+use lightvgl_sys::lv_draw_buf_t;
+
+unsafe extern "C" {
+    fn lodepng_encode32(
+        out: *mut *mut u8,
+        outsize: *mut usize,
+        image: *const u8,
+        w: c_uint,
+        h: c_uint,
+    ) -> c_uint;
+    fn lodepng_decode32(
+        out: *mut *mut u8,
+        w: *mut c_uint,
+        h: *mut c_uint,
+        in_: *const u8,
+        insize: usize,
+    ) -> c_uint;
+}
+
+/// LodePNG's "memory allocation failed" error
+const ERROR_ALLOCATION: c_uint = 83;
+/// LodePNG's "integer overflow due to too many pixels" error
+const ERROR_OVERFLOW: c_uint = 92;
+
+/// Encodes 8-bit RGBA pixels, returns the LodePNG error code on failure
+pub(crate) fn encode_png(rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>, c_uint> {
+    let len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .ok_or(ERROR_OVERFLOW)?;
+    assert_eq!(rgba.len(), len, "The image must have 4 bytes per pixel");
+    unsafe {
+        let mut out = ptr::null_mut();
+        let mut outsize = 0;
+        let error = lodepng_encode32(&mut out, &mut outsize, rgba.as_ptr(), width, height);
+        let png = take_lv_buffer(out, outsize);
+        if error != 0 {
+            return Err(error);
+        }
+        Ok(png)
+    }
+}
+
+/// Decodes a PNG into 8-bit RGBA pixels and returns them with the width and height
+///
+/// The LodePNG copy of LVGL decodes into an ARGB8888 `lv_draw_buf_t`, it is converted back to
+/// tightly packed RGBA.
+///
+/// The round trip through [`encode_png()`] is checked by the `compare_golden()` doctest.
+#[cfg_attr(not(feature = "golden"), expect(dead_code))]
+pub(crate) fn decode_png(png: &[u8]) -> Result<(Vec<u8>, u32, u32), c_uint> {
+    unsafe {
+        let mut out = ptr::null_mut();
+        let mut width = 0;
+        let mut height = 0;
+        let error = lodepng_decode32(&mut out, &mut width, &mut height, png.as_ptr(), png.len());
+        let Some(draw_buf) = NonNull::new(out.cast::<lv_draw_buf_t>()) else {
+            return Err(if error != 0 { error } else { ERROR_ALLOCATION });
+        };
+        let rgba = argb8888_to_rgba(draw_buf.as_ref(), width, height);
+        lightvgl_sys::lv_draw_buf_destroy(draw_buf.as_ptr());
+        if error != 0 {
+            return Err(error);
+        }
+        Ok((rgba?, width, height))
+    }
+}
+
+/// Copies the rows of an ARGB8888 draw buffer (blue, green, red, alpha in memory) without
+/// their padding and swaps the channels to RGBA
+fn argb8888_to_rgba(draw_buf: &lv_draw_buf_t, width: u32, height: u32) -> Result<Vec<u8>, c_uint> {
+    let height = height as usize;
+    let stride = draw_buf.header.stride() as usize;
+    let row_size = (width as usize).checked_mul(4).ok_or(ERROR_OVERFLOW)?;
+    let len = row_size.checked_mul(height).ok_or(ERROR_OVERFLOW)?;
+    let data_size = stride.checked_mul(height).ok_or(ERROR_OVERFLOW)?;
+    if draw_buf.data.is_null() || stride < row_size || (draw_buf.data_size as usize) < data_size {
+        return Err(ERROR_ALLOCATION);
+    }
+
+    let data = unsafe { ::core::slice::from_raw_parts(draw_buf.data, data_size) };
+    let mut rgba = Vec::with_capacity(len);
+    for row in data.chunks_exact(stride) {
+        let (pixels, _) = row.as_chunks::<4>();
+        for [blue, green, red, alpha] in pixels.iter().take(width as usize) {
+            rgba.extend_from_slice(&[*red, *green, *blue, *alpha]);
+        }
+    }
+    Ok(rgba)
+}
+
+/// Copies a buffer allocated by LodePNG and frees it with `lv_free()`
+unsafe fn take_lv_buffer(buffer: *mut u8, len: usize) -> Vec<u8> {
+    if buffer.is_null() {
+        return Vec::new();
+    }
+    unsafe {
+        let vec = ::core::slice::from_raw_parts(buffer, len).to_vec();
+        lightvgl_sys::lv_free(buffer.cast());
+        vec
+    }
+}
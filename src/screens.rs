@@ -0,0 +1,273 @@
+//! # Screens
+//!
+//! A [`Screen`] is a top-level object, its widgets are added as child entities.
+//! The [`ScreenNavigator`] resource keeps a stack of screens and loads them with an animation.
+//!
+//! ```
+//! # use core::time::Duration;
+//! # use lv_bevy_ecs::screens::{Screen, ScreenLoadAnim, ScreenNavigator};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! let mut world = LvglWorld::default();
+//!
+//! let home = world.spawn(Screen::create()).with_child(Label::new().into_inner()).id();
+//! let settings = world.spawn(Screen::create()).with_child(Button::new().into_inner()).id();
+//!
+//! ScreenNavigator::push(&mut world, home, ScreenLoadAnim::None, Duration::ZERO);
+//! ScreenNavigator::push(&mut world, settings, ScreenLoadAnim::MoveLeft, Duration::from_millis(300));
+//! assert_eq!(world.resource::<ScreenNavigator>().current(), Some(settings));
+//!
+//! ScreenNavigator::pop(&mut world, ScreenLoadAnim::MoveRight, Duration::from_millis(300));
+//! assert_eq!(world.resource::<ScreenNavigator>().current(), Some(home));
+//! ```
+
+use ::alloc::{sync::Arc, vec::Vec};
+use ::core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use bevy_ecs::{component::Component, entity::Entity, resource::Resource, world::World};
+use lightvgl_sys::lv_event_t;
+
+use crate::widgets::{RawObj, Widget};
+
+/// Marks the entity of a top-level object
+#[derive(Component, Default)]
+pub struct Screen;
+
+impl Screen {
+    /// Creates a top-level object on the default display, spawn the returned bundle
+    pub fn create() -> (Self, Widget) {
+        unsafe {
+            let raw = lightvgl_sys::lv_obj_create(::core::ptr::null_mut());
+            (Self, Widget::from_ptr(raw).unwrap())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum ScreenLoadAnim {
+    None = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_NONE,
+    OverLeft = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OVER_LEFT,
+    OverRight = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OVER_RIGHT,
+    OverTop = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OVER_TOP,
+    OverBottom = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OVER_BOTTOM,
+    MoveLeft = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_MOVE_LEFT,
+    MoveRight = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_MOVE_RIGHT,
+    MoveTop = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_MOVE_TOP,
+    MoveBottom = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_MOVE_BOTTOM,
+    FadeIn = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_FADE_IN,
+    FadeOut = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_FADE_OUT,
+    OutLeft = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OUT_LEFT,
+    OutRight = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OUT_RIGHT,
+    OutTop = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OUT_TOP,
+    OutBottom = lightvgl_sys::lv_screen_load_anim_t_LV_SCREEN_LOAD_ANIM_OUT_BOTTOM,
+}
+
+impl From<ScreenLoadAnim> for lightvgl_sys::lv_screen_load_anim_t {
+    #[inline]
+    fn from(value: ScreenLoadAnim) -> Self {
+        value as Self
+    }
+}
+
+/// Stack of screen entities, the last one is the active screen
+///
+/// With [`set_auto_despawn(true)`](ScreenNavigator::set_auto_despawn) popped and replaced
+/// screens are despawned together with their children once their unload animation has finished.
+#[derive(Resource, Default)]
+pub struct ScreenNavigator {
+    stack: Vec<Entity>,
+    unloading: Vec<Unloading>,
+    auto_despawn: bool,
+}
+
+/// A screen that is despawned when LVGL sends `LV_EVENT_SCREEN_UNLOADED`
+struct Unloading {
+    entity: Entity,
+    /// Set by [`screen_unloaded()`], the screen holds another reference until it is deleted
+    unloaded: Arc<AtomicBool>,
+}
+
+impl ScreenNavigator {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn set_auto_despawn(&mut self, auto_despawn: bool) {
+        self.auto_despawn = auto_despawn;
+    }
+
+    /// Despawns `screen` once it has been unloaded, right away if it is not loaded
+    pub fn despawn_when_unloaded(world: &mut World, screen: Entity) {
+        world.init_resource::<Self>();
+        if world
+            .resource::<Self>()
+            .unloading
+            .iter()
+            .any(|unloading| unloading.entity == screen)
+        {
+            return;
+        }
+
+        let raw = world
+            .get_mut::<Widget>(screen)
+            .map(|mut widget| widget.raw_mut());
+        let loaded = raw.is_some_and(|raw| unsafe {
+            let display = lightvgl_sys::lv_obj_get_display(raw);
+            lightvgl_sys::lv_display_get_screen_active(display) == raw
+                || lightvgl_sys::lv_display_get_screen_prev(display) == raw
+        });
+        let Some(raw) = raw.filter(|_| loaded) else {
+            world.despawn(screen);
+            return;
+        };
+
+        let unloaded = Arc::new(AtomicBool::new(false));
+        unsafe {
+            let user_data = Arc::into_raw(Arc::clone(&unloaded)).cast_mut().cast();
+            lightvgl_sys::lv_obj_add_event_cb(
+                raw,
+                Some(screen_unloaded),
+                lightvgl_sys::lv_event_code_t_LV_EVENT_SCREEN_UNLOADED,
+                user_data,
+            );
+            lightvgl_sys::lv_obj_add_event_cb(
+                raw,
+                Some(release_unloaded),
+                lightvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+                user_data,
+            );
+        }
+        world.resource_mut::<Self>().unloading.push(Unloading {
+            entity: screen,
+            unloaded,
+        });
+    }
+
+    /// Returns the entity of the active screen
+    #[inline]
+    pub fn current(&self) -> Option<Entity> {
+        self.stack.last().copied()
+    }
+
+    /// Returns the screen entities from the first to the active one
+    #[inline]
+    pub fn stack(&self) -> &[Entity] {
+        &self.stack
+    }
+
+    /// Loads `screen` and puts it on top of the stack
+    pub fn push(world: &mut World, screen: Entity, anim: ScreenLoadAnim, duration: Duration) {
+        world.init_resource::<Self>();
+        load_screen(world, screen, anim, duration);
+        world.resource_mut::<Self>().stack.push(screen);
+    }
+
+    /// Removes the active screen from the stack and loads the previous one
+    ///
+    /// Returns the removed screen, it is only despawned if auto-despawn is enabled.
+    pub fn pop(world: &mut World, anim: ScreenLoadAnim, duration: Duration) -> Option<Entity> {
+        world.init_resource::<Self>();
+        let mut navigator = world.resource_mut::<Self>();
+        if navigator.stack.len() < 2 {
+            return None;
+        }
+        let previous = navigator.stack.pop()?;
+        let current = navigator.current()?;
+        let auto_despawn = navigator.auto_despawn;
+        if auto_despawn {
+            // registered before loading, a load without animation unloads immediately
+            Self::despawn_when_unloaded(world, previous);
+        }
+        load_screen(world, current, anim, duration);
+        Some(previous)
+    }
+
+    /// Loads `screen` in place of the active screen
+    ///
+    /// Returns the replaced screen, it is only despawned if auto-despawn is enabled.
+    pub fn replace(
+        world: &mut World,
+        screen: Entity,
+        anim: ScreenLoadAnim,
+        duration: Duration,
+    ) -> Option<Entity> {
+        world.init_resource::<Self>();
+        let mut navigator = world.resource_mut::<Self>();
+        let previous = navigator.stack.pop();
+        navigator.stack.push(screen);
+        let auto_despawn = navigator.auto_despawn;
+        if auto_despawn && let Some(previous) = previous {
+            Self::despawn_when_unloaded(world, previous);
+        }
+        load_screen(world, screen, anim, duration);
+        previous
+    }
+
+    /// Despawns the screens that received `LV_EVENT_SCREEN_UNLOADED` since the last call
+    ///
+    /// Screens that were despawned elsewhere are forgotten.
+    /// `LvglPlugin` runs it in `PostUpdate`, without the plugin call it after `lv_timer_handler()`.
+    pub fn despawn_unloaded(world: &mut World) {
+        world.init_resource::<Self>();
+        let unloading = ::core::mem::take(&mut world.resource_mut::<Self>().unloading);
+        for unloading in unloading {
+            if world.get_entity(unloading.entity).is_err() {
+                continue;
+            }
+            if unloading.unloaded.load(Ordering::Acquire) {
+                world.despawn(unloading.entity);
+            } else {
+                world.resource_mut::<Self>().unloading.push(unloading);
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn screen_unloaded(event: *mut lv_event_t) {
+    unsafe {
+        let unloaded = lightvgl_sys::lv_event_get_user_data(event).cast::<AtomicBool>();
+        if let Some(unloaded) = unloaded.as_ref() {
+            unloaded.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Releases the reference of the screen, however it was deleted
+unsafe extern "C" fn release_unloaded(event: *mut lv_event_t) {
+    unsafe {
+        let unloaded = lightvgl_sys::lv_event_get_user_data(event).cast::<AtomicBool>();
+        if !unloaded.is_null() {
+            drop(Arc::from_raw(unloaded));
+        }
+    }
+}
+
+fn load_screen(world: &mut World, screen: Entity, anim: ScreenLoadAnim, duration: Duration) {
+    // a screen that is loaded again stays
+    world
+        .resource_mut::<ScreenNavigator>()
+        .unloading
+        .retain(|unloading| unloading.entity != screen);
+    let mut widget = world
+        .get_mut::<Widget>(screen)
+        .expect("Screen entity has no Widget");
+    unsafe {
+        // the entity owns the old screen, LVGL must not delete it
+        lightvgl_sys::lv_screen_load_anim(
+            widget.raw_mut(),
+            anim.into(),
+            duration.as_millis().try_into().unwrap_or(u32::MAX),
+            0,
+            false,
+        );
+    }
+}
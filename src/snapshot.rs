@@ -0,0 +1,213 @@
+//! # Snapshots
+//!
+//! [`Wdg::snapshot()`] renders a widget and its children into an owned [`ImageBuffer`]
+//! (requires `LV_USE_SNAPSHOT`). The buffer can be drawn with embedded-graphics,
+//! shown in an `Image` widget or encoded to PNG (requires `LV_USE_LODEPNG`).
+//!
+//! ```
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use lv_bevy_ecs::support::{ColorFormat, ToVoid};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! let mut button = Button::new();
+//! button.set_size(100, 50);
+//!
+//! let snapshot = button.snapshot(ColorFormat::Rgb565).unwrap();
+//! assert!(snapshot.width() >= 100 && snapshot.height() >= 50);
+//!
+//! let image_raw = snapshot.as_image_raw::<Rgb565>().unwrap();
+//! let png = snapshot.to_png().unwrap();
+//!
+//! // the buffer must outlive the image widget
+//! let mut world = LvglWorld::default();
+//! let mut image = Image::new();
+//! image.set_src(snapshot.to_void());
+//! world.spawn(button.into_inner());
+//! world.spawn(image.into_inner());
+//! ```
+
+use ::alloc::vec::Vec;
+use ::core::{ffi::c_uint, ops::Deref, ptr::NonNull};
+
+use embedded_graphics::{
+    image::ImageRaw,
+    pixelcolor::{
+        Rgb565, Rgb888, RgbColor,
+        raw::{LittleEndian, RawData, RawU16},
+    },
+    prelude::PixelColor,
+};
+use lightvgl_sys::{lv_color_format_t, lv_draw_buf_t};
+use thiserror::Error;
+
+#[cfg(LV_USE_LODEPNG)]
+use crate::png::encode_png;
+use crate::{
+    support::{ColorFormat, LvglColorFormat},
+    widgets::{RawObj, Wdg},
+};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    #[error("lv_snapshot_take failed (out of memory or unsupported color format)")]
+    Failed,
+    #[error("color format {0} cannot be converted to PNG")]
+    UnsupportedFormat(lv_color_format_t),
+    #[error("LodePNG error {0}")]
+    Png(c_uint),
+}
+
+impl Wdg {
+    /// Renders the widget and its children into a new [`ImageBuffer`]
+    pub fn snapshot(&mut self, format: ColorFormat) -> Result<ImageBuffer, SnapshotError> {
+        unsafe {
+            let raw = lightvgl_sys::lv_snapshot_take(self.raw_mut(), format.into());
+            NonNull::new(raw)
+                .map(|raw| ImageBuffer { raw })
+                .ok_or(SnapshotError::Failed)
+        }
+    }
+}
+
+/// An owned `lv_draw_buf_t`, destroyed when dropped
+///
+/// It can be passed to `Image::set_src()` with [`to_void()`](crate::support::ToVoid::to_void).
+pub struct ImageBuffer {
+    raw: NonNull<lv_draw_buf_t>,
+}
+
+impl ImageBuffer {
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.header.w()
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.header.h()
+    }
+
+    /// Number of bytes in a row, including padding
+    #[inline]
+    pub fn stride(&self) -> u32 {
+        self.header.stride()
+    }
+
+    #[inline]
+    pub fn color_format(&self) -> lv_color_format_t {
+        self.header.cf() as lv_color_format_t
+    }
+
+    /// Pixel data, including the palette of indexed formats
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        unsafe { ::core::slice::from_raw_parts(self.data, self.data_size as usize) }
+    }
+
+    /// Returns an embedded-graphics image of the buffer, or `None` if `C` does not match the
+    /// color format or the rows are padded
+    #[expect(clippy::arithmetic_side_effects)]
+    pub fn as_image_raw<C>(&self) -> Option<ImageRaw<'_, C, LittleEndian>>
+    where
+        C: LvglColorFormat + From<<C as PixelColor>::Raw>,
+    {
+        if self.color_format() != C::as_lv_color_format_t() {
+            return None;
+        }
+        let bits_per_pixel = C::Raw::BITS_PER_PIXEL as u32;
+        if self.stride() != (self.width() * bits_per_pixel).div_ceil(8) {
+            return None;
+        }
+        let len = (self.stride() * self.height()) as usize;
+        let pixels = self.data().get(C::PALETTE_SIZE..)?.get(..len)?;
+        Some(ImageRaw::new(pixels, self.width()))
+    }
+
+    /// Encodes the buffer as an RGBA PNG
+    #[cfg(LV_USE_LODEPNG)]
+    pub fn to_png(&self) -> Result<Vec<u8>, SnapshotError> {
+        let rgba = self
+            .to_rgba()
+            .ok_or(SnapshotError::UnsupportedFormat(self.color_format()))?;
+        encode_png(&rgba, self.width(), self.height()).map_err(SnapshotError::Png)
+    }
+
+    /// Converts the pixels to tightly packed 8-bit RGBA, or `None` if the color format is not supported
+    #[expect(clippy::arithmetic_side_effects)]
+    pub fn to_rgba(&self) -> Option<Vec<u8>> {
+        let cf = self.color_format();
+        let bytes_per_pixel = match cf {
+            lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_L8 => 1,
+            lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565
+            | lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565_SWAPPED => 2,
+            lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB888 => 3,
+            lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_ARGB8888
+            | lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_XRGB8888 => 4,
+            _ => return None,
+        };
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for row in self.data().chunks(self.stride() as usize).take(height) {
+            for pixel in row.chunks_exact(bytes_per_pixel).take(width) {
+                let color = match (cf, pixel) {
+                    (lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_L8, &[l]) => [l, l, l, 255],
+                    (lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565, &[low, high]) => {
+                        rgb565_to_rgba(u16::from_le_bytes([low, high]))
+                    }
+                    (
+                        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565_SWAPPED,
+                        &[high, low],
+                    ) => rgb565_to_rgba(u16::from_le_bytes([low, high])),
+                    (lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB888, &[b, g, r]) => {
+                        [r, g, b, 255]
+                    }
+                    (lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_ARGB8888, &[b, g, r, a]) => {
+                        [r, g, b, a]
+                    }
+                    (lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_XRGB8888, &[b, g, r, _]) => {
+                        [r, g, b, 255]
+                    }
+                    _ => return None,
+                };
+                rgba.extend_from_slice(&color);
+            }
+        }
+        Some(rgba)
+    }
+
+    #[inline]
+    pub fn raw(&self) -> *const lv_draw_buf_t {
+        self.raw.as_ptr().cast_const()
+    }
+
+    #[inline]
+    pub fn raw_mut(&mut self) -> *mut lv_draw_buf_t {
+        self.raw.as_ptr()
+    }
+}
+
+fn rgb565_to_rgba(raw: u16) -> [u8; 4] {
+    let color = Rgb888::from(Rgb565::from(RawU16::new(raw)));
+    [color.r(), color.g(), color.b(), 255]
+}
+
+impl Deref for ImageBuffer {
+    type Target = lv_draw_buf_t;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.raw.as_ref() }
+    }
+}
+
+impl Drop for ImageBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            lightvgl_sys::lv_draw_buf_destroy(self.raw.as_ptr());
+        }
+    }
+}
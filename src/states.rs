@@ -0,0 +1,238 @@
+//! # States
+//!
+//! Screen switching driven by Bevy [`States`]. Every state is mapped to a system that builds
+//! its [`Screen`](crate::screens::Screen) and returns the entity. The screens are hooked into the
+//! `OnEnter` and `OnExit` schedules of the [`StateTransition`] schedule: entering a state loads
+//! its new screen through the [`ScreenNavigator`] with the animation configured for the pair of
+//! states, exiting it despawns the old screen together with its children once it is unloaded.
+//!
+//! `LvglWorld` has no `App`, so [`init_screen_state()`](ScreenStatesExt::init_screen_state) sets
+//! up the [`StateTransition`] schedule like `App::init_state()` does, and
+//! [`update_screen_state()`](ScreenStatesExt::update_screen_state) runs it, call it after changing
+//! [`NextState`]. `OnTransition` schedules run and [`StateTransitionEvent`]s are sent as usual.
+//!
+//! ```
+//! # use core::time::Duration;
+//! # use bevy_state::prelude::*;
+//! # use lv_bevy_ecs::bevy::prelude::*;
+//! # use lv_bevy_ecs::screens::{Screen, ScreenLoadAnim};
+//! # use lv_bevy_ecs::states::ScreenStatesExt;
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+//! enum AppState {
+//!     #[default]
+//!     Boot,
+//!     Home,
+//! }
+//!
+//! fn boot_screen(mut commands: Commands) -> Entity {
+//!     let mut label = Label::new();
+//!     label.set_text(c"Booting...");
+//!     commands.spawn(Screen::create()).with_child(label.into_inner()).id()
+//! }
+//!
+//! fn home_screen(mut commands: Commands) -> Entity {
+//!     let button = Button::new();
+//!     commands.spawn(Screen::create()).with_child(button.into_inner()).id()
+//! }
+//!
+//! let mut world = LvglWorld::default();
+//! world.add_state_screen(AppState::Boot, boot_screen);
+//! world.add_state_screen(AppState::Home, home_screen);
+//! world.set_state_transition(
+//!     AppState::Boot,
+//!     AppState::Home,
+//!     ScreenLoadAnim::FadeIn,
+//!     Duration::from_millis(500),
+//! );
+//! world.init_screen_state::<AppState>();
+//!
+//! world.resource_mut::<NextState<AppState>>().set(AppState::Home);
+//! assert!(world.update_screen_state::<AppState>());
+//! assert_eq!(world.resource::<State<AppState>>().get(), &AppState::Home);
+//! ```
+
+use ::alloc::vec::Vec;
+use ::core::time::Duration;
+
+use bevy_ecs::{
+    entity::Entity,
+    message::Messages,
+    resource::Resource,
+    schedule::Schedules,
+    system::IntoSystem,
+    world::{FromWorld, Mut, World},
+};
+use bevy_state::state::{
+    FreelyMutableState, NextState, OnEnter, OnExit, State, StateTransition, StateTransitionEvent,
+    States, last_transition, setup_state_transitions_in_world,
+};
+
+use crate::screens::{ScreenLoadAnim, ScreenNavigator};
+
+/// Connects [`States`] to screens, implemented for `World` and therefore for `LvglWorld`
+pub trait ScreenStatesExt {
+    /// Runs `build` when `state` is entered and loads the screen entity it returns,
+    /// the screen is despawned when the state is exited
+    fn add_state_screen<S, M>(
+        &mut self,
+        state: S,
+        build: impl IntoSystem<(), Entity, M> + 'static,
+    ) -> &mut Self
+    where
+        S: States;
+
+    /// Sets the animation used when the state changes from `exited` to `entered`
+    fn set_state_transition<S: States>(
+        &mut self,
+        exited: S,
+        entered: S,
+        anim: ScreenLoadAnim,
+        duration: Duration,
+    ) -> &mut Self;
+
+    /// Sets the animation of the state changes without a transition of their own
+    fn set_default_state_transition<S: States>(
+        &mut self,
+        anim: ScreenLoadAnim,
+        duration: Duration,
+    ) -> &mut Self;
+
+    /// Inserts the [`State`] and [`NextState`] resources, registers the state in the
+    /// [`StateTransition`] schedule and enters the initial state
+    fn init_screen_state<S: FreelyMutableState + FromWorld>(&mut self) -> &mut Self;
+
+    /// Runs the [`StateTransition`] schedule to apply the pending [`NextState`],
+    /// returns whether a transition of `S` happened
+    fn update_screen_state<S: FreelyMutableState>(&mut self) -> bool;
+}
+
+impl ScreenStatesExt for World {
+    fn add_state_screen<S, M>(
+        &mut self,
+        state: S,
+        build: impl IntoSystem<(), Entity, M> + 'static,
+    ) -> &mut Self
+    where
+        S: States,
+    {
+        let build = self.register_system(build);
+        let entered = state.clone();
+        let mut schedules = self.get_resource_or_init::<Schedules>();
+        schedules.add_systems(OnEnter(state.clone()), move |world: &mut World| {
+            let Ok(screen) = world.run_system(build) else {
+                crate::warn!("Could not build the screen of the entered state");
+                return;
+            };
+            let exited = world
+                .run_system_cached(last_transition::<S>)
+                .ok()
+                .flatten()
+                .and_then(|transition| transition.exited);
+            let mut transitions = transitions_mut::<S>(world);
+            transitions.screen = Some(screen);
+            let (anim, duration) = transitions.get(exited.as_ref(), &entered);
+            ScreenNavigator::replace(world, screen, anim, duration);
+        });
+        schedules.add_systems(OnExit(state), |world: &mut World| {
+            // the old screen is still loaded, it is despawned after its unload animation
+            if let Some(screen) = transitions_mut::<S>(world).screen.take() {
+                ScreenNavigator::despawn_when_unloaded(world, screen);
+            }
+        });
+        self
+    }
+
+    fn set_state_transition<S: States>(
+        &mut self,
+        exited: S,
+        entered: S,
+        anim: ScreenLoadAnim,
+        duration: Duration,
+    ) -> &mut Self {
+        let mut transitions = transitions_mut::<S>(self);
+        transitions
+            .transitions
+            .retain(|(from, to, _)| (from, to) != (&exited, &entered));
+        transitions
+            .transitions
+            .push((exited, entered, (anim, duration)));
+        self
+    }
+
+    fn set_default_state_transition<S: States>(
+        &mut self,
+        anim: ScreenLoadAnim,
+        duration: Duration,
+    ) -> &mut Self {
+        transitions_mut::<S>(self).default = (anim, duration);
+        self
+    }
+
+    fn init_screen_state<S: FreelyMutableState + FromWorld>(&mut self) -> &mut Self {
+        // same as `App::init_state()`
+        setup_state_transitions_in_world(self);
+        if !self.contains_resource::<State<S>>() {
+            let initial = S::from_world(self);
+            self.insert_resource(State::new(initial.clone()));
+            self.init_resource::<NextState<S>>();
+            self.init_resource::<Messages<StateTransitionEvent<S>>>();
+            if let Some(schedule) = self.resource_mut::<Schedules>().get_mut(StateTransition) {
+                S::register_state(schedule);
+            }
+            self.write_message(StateTransitionEvent {
+                exited: None,
+                entered: Some(initial),
+                allow_same_state_transitions: true,
+            });
+        }
+        self.update_screen_state::<S>();
+        self
+    }
+
+    fn update_screen_state<S: FreelyMutableState>(&mut self) -> bool {
+        let Some(mut messages) = self.get_resource_mut::<Messages<StateTransitionEvent<S>>>()
+        else {
+            return false;
+        };
+        // the readers of the schedule have seen the messages of the previous run
+        messages.update();
+        let sent = messages.len();
+        self.try_run_schedule(StateTransition).unwrap_or_default();
+        self.resource::<Messages<StateTransitionEvent<S>>>().len() > sent
+    }
+}
+
+#[derive(Resource)]
+struct ScreenTransitions<S: States> {
+    default: (ScreenLoadAnim, Duration),
+    transitions: Vec<(S, S, (ScreenLoadAnim, Duration))>,
+    /// The screen of the current state
+    screen: Option<Entity>,
+}
+
+impl<S: States> ScreenTransitions<S> {
+    fn get(&self, exited: Option<&S>, entered: &S) -> (ScreenLoadAnim, Duration) {
+        self.transitions
+            .iter()
+            .find(|(from, to, _)| Some(from) == exited && to == entered)
+            .map_or(self.default, |(_, _, transition)| *transition)
+    }
+}
+
+impl<S: States> Default for ScreenTransitions<S> {
+    fn default() -> Self {
+        Self {
+            default: (ScreenLoadAnim::None, Duration::ZERO),
+            transitions: Vec::new(),
+            screen: None,
+        }
+    }
+}
+
+fn transitions_mut<S: States>(world: &mut World) -> Mut<'_, ScreenTransitions<S>> {
+    world.get_resource_or_init::<ScreenTransitions<S>>()
+}
@@ -0,0 +1,459 @@
+//! # Style sheets
+//!
+//! With the `serde` feature, styles can be described in any self-describing format
+//! (RON, TOML, JSON...) and built into [`Style`]s at runtime, so a theme can be tweaked
+//! without recompiling.
+//!
+//! A [`StyleSheetDesc`] contains named styles and the styles a [`Theme`] adds to each widget
+//! class. Every style has an optional `selector` (e.g. `"knob | pressed"`) and properties named
+//! after the `Style::set_*` methods. Sizes, positions and paddings are pixels, `"50%"` or
+//! `"content"`. Colors are `"#RRGGBB"`, `"#RGB"` or integers, fonts are looked up by name in
+//! [`Fonts`] and simple gradients are described in a `bg_grad` table.
+//!
+//! ```
+//! # use lv_bevy_ecs::style_sheet::{Fonts, StyleSheetDesc, StyleSheetError};
+//! # use lv_bevy_ecs::styles::Part;
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! let desc: StyleSheetDesc = toml::from_str(r##"
+//!     [styles.card]
+//!     bg_color = "#2196F3"
+//!     radius = 12
+//!     pad_all = 8
+//!     text_font = "big"
+//!     bg_grad = { color = "#0D47A1", dir = "vertical" }
+//!
+//!     [styles.pressed]
+//!     selector = "main | pressed"
+//!     bg_opa = 200
+//!
+//!     [widgets]
+//!     lv_button = ["card", "pressed"]
+//! "##).unwrap();
+//!
+//! let fonts = Fonts::new().with_font("big", unsafe { &lv_bevy_ecs::sys::lv_font_montserrat_24 });
+//! let sheet = desc.build(&fonts).unwrap();
+//!
+//! let mut world = LvglWorld::default();
+//! let button = world.spawn((Button::new().into_inner(), sheet.style("card").unwrap())).id();
+//! let widget = world.get::<Widget>(button).unwrap();
+//! assert_eq!(widget.get_style_radius(Part::Main), 12);
+//!
+//! // errors point to the offending field
+//! let desc: StyleSheetDesc = toml::from_str("[styles.card]\nradius = \"big\"").unwrap();
+//! let Err(StyleSheetError::TypeMismatch { path, .. }) = desc.build(&fonts) else {
+//!     panic!()
+//! };
+//! assert_eq!(path, "styles.card.radius");
+//! ```
+
+use ::alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec};
+use ::core::ffi::CStr;
+
+use lightvgl_sys::{lv_font_t, lv_grad_dir_t, lv_opa_t, lv_text_align_t};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    colors::Color,
+    styles::{Part, Selector, State, Style, StyleHandle},
+    support::Coord,
+    themes::Theme,
+};
+
+/// A value of a style property
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Table(BTreeMap<String, Value>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Bool(_) => "boolean",
+            Self::Int(_) => "integer",
+            Self::Float(_) => "float",
+            Self::String(_) => "string",
+            Self::Table(_) => "table",
+        }
+    }
+}
+
+/// Description of a single [`Style`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StyleDesc {
+    /// A part and states separated by `|`, e.g. `"indicator | checked"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    #[serde(flatten)]
+    pub properties: BTreeMap<String, Value>,
+}
+
+impl StyleDesc {
+    /// Builds the style, errors are reported relative to the style
+    pub fn build(&self, fonts: &Fonts) -> Result<Style, StyleSheetError> {
+        self.build_at("", fonts)
+    }
+
+    fn build_at(&self, path: &str, fonts: &Fonts) -> Result<Style, StyleSheetError> {
+        let selector = match &self.selector {
+            Some(selector) => parse_selector(&join(path, "selector"), selector)?,
+            None => Selector::default(),
+        };
+        let mut style = Style::new(selector);
+        for (name, value) in &self.properties {
+            set_property(&mut style, &join(path, name), name, value, fonts)?;
+        }
+        Ok(style)
+    }
+}
+
+/// Named styles and the styles of each widget class
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleSheetDesc {
+    pub styles: BTreeMap<String, StyleDesc>,
+    /// Style names by LVGL class name, e.g. `lv_button`, used by [`StyleSheet::to_theme()`]
+    pub widgets: BTreeMap<String, Vec<String>>,
+}
+
+impl StyleSheetDesc {
+    pub fn build(&self, fonts: &Fonts) -> Result<StyleSheet, StyleSheetError> {
+        let mut styles = BTreeMap::new();
+        for (name, desc) in &self.styles {
+            let style = desc.build_at(&join("styles", name), fonts)?;
+            styles.insert(name.clone(), StyleHandle::new(style));
+        }
+        let mut widgets = Vec::new();
+        for (class, names) in &self.widgets {
+            let path = join("widgets", class);
+            let handles = names
+                .iter()
+                .map(|name| {
+                    styles
+                        .get(name)
+                        .cloned()
+                        .ok_or_else(|| StyleSheetError::UnknownStyle {
+                            path: path.clone(),
+                            name: name.clone(),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            widgets.push((class.clone(), handles));
+        }
+        Ok(StyleSheet { styles, widgets })
+    }
+}
+
+/// Styles built from a [`StyleSheetDesc`]
+pub struct StyleSheet {
+    styles: BTreeMap<String, StyleHandle>,
+    widgets: Vec<(String, Vec<StyleHandle>)>,
+}
+
+impl StyleSheet {
+    /// Returns the shared style, insert clones of it into many entities
+    #[inline]
+    pub fn handle(&self, name: &str) -> Option<&StyleHandle> {
+        self.styles.get(name)
+    }
+
+    /// Returns a copy of the style
+    pub fn style(&self, name: &str) -> Option<Style> {
+        self.styles.get(name).map(|handle| handle.style().clone())
+    }
+
+    /// Creates a theme that adds the styles listed under `widgets` to the matching classes
+    pub fn to_theme(&self, parent: Option<&Theme>) -> Theme {
+        let widgets = self.widgets.clone();
+        Theme::new(parent, move |widget, class| {
+            let Some(name) = class.name().map(CStr::to_bytes) else {
+                return;
+            };
+            for (_, handles) in widgets.iter().filter(|(class, _)| class.as_bytes() == name) {
+                for handle in handles {
+                    widget.add_style(handle);
+                }
+            }
+        })
+    }
+}
+
+/// Fonts that can be referenced by name in style descriptions
+#[derive(Default, Clone)]
+pub struct Fonts {
+    fonts: BTreeMap<String, &'static lv_font_t>,
+}
+
+impl Fonts {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_font(mut self, name: &str, font: &'static lv_font_t) -> Self {
+        self.fonts.insert(name.to_owned(), font);
+        self
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&'static lv_font_t> {
+        self.fonts.get(name).copied()
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum StyleSheetError {
+    #[error("{path}: unknown property")]
+    UnknownProperty { path: String },
+    #[error("{path}: expected {expected}, found {found}")]
+    TypeMismatch {
+        path: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("{path}: {value} is out of range")]
+    OutOfRange { path: String, value: i64 },
+    #[error("{path}: invalid value {value:?}")]
+    InvalidValue { path: String, value: String },
+    #[error("{path}: unknown font {name:?}")]
+    UnknownFont { path: String, name: String },
+    #[error("{path}: unknown style {name:?}")]
+    UnknownStyle { path: String, name: String },
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_owned()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+fn set_property(
+    style: &mut Style,
+    path: &str,
+    name: &str,
+    value: &Value,
+    fonts: &Fonts,
+) -> Result<(), StyleSheetError> {
+    match name {
+        "width" => style.set_width(coord(path, value)?),
+        "height" => style.set_height(coord(path, value)?),
+        "min_width" => style.set_min_width(coord(path, value)?),
+        "max_width" => style.set_max_width(coord(path, value)?),
+        "min_height" => style.set_min_height(coord(path, value)?),
+        "max_height" => style.set_max_height(coord(path, value)?),
+        "x" => style.set_x(coord(path, value)?),
+        "y" => style.set_y(coord(path, value)?),
+        "pad_all" => {
+            let pad = coord(path, value)?;
+            style.set_pad_top(pad);
+            style.set_pad_bottom(pad);
+            style.set_pad_left(pad);
+            style.set_pad_right(pad);
+        }
+        "pad_top" => style.set_pad_top(coord(path, value)?),
+        "pad_bottom" => style.set_pad_bottom(coord(path, value)?),
+        "pad_left" => style.set_pad_left(coord(path, value)?),
+        "pad_right" => style.set_pad_right(coord(path, value)?),
+        "pad_row" => style.set_pad_row(coord(path, value)?),
+        "pad_column" => style.set_pad_column(coord(path, value)?),
+        "radius" => style.set_radius(int(path, value)?),
+        "opa" => style.set_opa(opa(path, value)?),
+        "bg_color" => style.set_bg_color(color(path, value)?),
+        "bg_opa" => style.set_bg_opa(opa(path, value)?),
+        "bg_grad" => set_gradient(style, path, value)?,
+        "border_color" => style.set_border_color(color(path, value)?),
+        "border_opa" => style.set_border_opa(opa(path, value)?),
+        "border_width" => style.set_border_width(int(path, value)?),
+        "outline_color" => style.set_outline_color(color(path, value)?),
+        "outline_opa" => style.set_outline_opa(opa(path, value)?),
+        "outline_width" => style.set_outline_width(int(path, value)?),
+        "outline_pad" => style.set_outline_pad(int(path, value)?),
+        "shadow_color" => style.set_shadow_color(color(path, value)?),
+        "shadow_opa" => style.set_shadow_opa(opa(path, value)?),
+        "shadow_width" => style.set_shadow_width(int(path, value)?),
+        "shadow_spread" => style.set_shadow_spread(int(path, value)?),
+        "shadow_offset_x" => style.set_shadow_offset_x(int(path, value)?),
+        "shadow_offset_y" => style.set_shadow_offset_y(int(path, value)?),
+        "text_color" => style.set_text_color(color(path, value)?),
+        "text_opa" => style.set_text_opa(opa(path, value)?),
+        "text_letter_space" => style.set_text_letter_space(int(path, value)?),
+        "text_line_space" => style.set_text_line_space(int(path, value)?),
+        "text_align" => style.set_text_align(text_align(path, value)?),
+        "text_font" => {
+            let name = string(path, value)?;
+            let font = fonts
+                .get(name)
+                .ok_or_else(|| StyleSheetError::UnknownFont {
+                    path: path.to_owned(),
+                    name: name.to_owned(),
+                })?;
+            unsafe {
+                style.set_text_font(font);
+            }
+        }
+        _ => {
+            return Err(StyleSheetError::UnknownProperty {
+                path: path.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn set_gradient(style: &mut Style, path: &str, value: &Value) -> Result<(), StyleSheetError> {
+    let Value::Table(table) = value else {
+        return Err(mismatch(path, "table", value));
+    };
+    for (name, value) in table {
+        let path = join(path, name);
+        match name.as_str() {
+            "color" => style.set_bg_grad_color(color(&path, value)?),
+            "dir" => style.set_bg_grad_dir(grad_dir(&path, value)?),
+            "main_stop" => style.set_bg_main_stop(int(&path, value)?),
+            "grad_stop" => style.set_bg_grad_stop(int(&path, value)?),
+            _ => return Err(StyleSheetError::UnknownProperty { path }),
+        }
+    }
+    Ok(())
+}
+
+fn mismatch(path: &str, expected: &'static str, value: &Value) -> StyleSheetError {
+    StyleSheetError::TypeMismatch {
+        path: path.to_owned(),
+        expected,
+        found: value.kind(),
+    }
+}
+
+fn invalid(path: &str, value: &str) -> StyleSheetError {
+    StyleSheetError::InvalidValue {
+        path: path.to_owned(),
+        value: value.to_owned(),
+    }
+}
+
+fn string<'a>(path: &str, value: &'a Value) -> Result<&'a str, StyleSheetError> {
+    match value {
+        Value::String(value) => Ok(value),
+        _ => Err(mismatch(path, "string", value)),
+    }
+}
+
+fn int(path: &str, value: &Value) -> Result<i32, StyleSheetError> {
+    match value {
+        Value::Int(value) => i32::try_from(*value).map_err(|_| StyleSheetError::OutOfRange {
+            path: path.to_owned(),
+            value: *value,
+        }),
+        _ => Err(mismatch(path, "integer", value)),
+    }
+}
+
+/// Pixels, `"50%"` or `"content"`
+fn coord(path: &str, value: &Value) -> Result<Coord, StyleSheetError> {
+    match value {
+        Value::String(text) if text == "content" => Ok(Coord::Content),
+        Value::String(text) => text
+            .strip_suffix('%')
+            .and_then(|pct| pct.trim().parse().ok())
+            .map(Coord::Pct)
+            .ok_or_else(|| invalid(path, text)),
+        _ => int(path, value).map(Coord::Px),
+    }
+}
+
+fn opa(path: &str, value: &Value) -> Result<lv_opa_t, StyleSheetError> {
+    match value {
+        Value::Int(value) => lv_opa_t::try_from(*value).map_err(|_| StyleSheetError::OutOfRange {
+            path: path.to_owned(),
+            value: *value,
+        }),
+        _ => Err(mismatch(path, "integer", value)),
+    }
+}
+
+fn color(path: &str, value: &Value) -> Result<Color, StyleSheetError> {
+    match value {
+        Value::Int(hex) => u32::try_from(*hex)
+            .ok()
+            .filter(|hex| *hex <= 0xFF_FFFF)
+            .map(Color::hex)
+            .ok_or(StyleSheetError::OutOfRange {
+                path: path.to_owned(),
+                value: *hex,
+            }),
+        Value::String(text) => {
+            let digits = text.strip_prefix('#').ok_or_else(|| invalid(path, text))?;
+            let hex = u32::from_str_radix(digits, 16).map_err(|_| invalid(path, text))?;
+            match digits.len() {
+                3 => Ok(Color::hex3(hex)),
+                6 => Ok(Color::hex(hex)),
+                _ => Err(invalid(path, text)),
+            }
+        }
+        _ => Err(mismatch(path, "color", value)),
+    }
+}
+
+fn grad_dir(path: &str, value: &Value) -> Result<lv_grad_dir_t, StyleSheetError> {
+    match string(path, value)? {
+        "none" => Ok(lightvgl_sys::lv_grad_dir_t_LV_GRAD_DIR_NONE),
+        "vertical" => Ok(lightvgl_sys::lv_grad_dir_t_LV_GRAD_DIR_VER),
+        "horizontal" => Ok(lightvgl_sys::lv_grad_dir_t_LV_GRAD_DIR_HOR),
+        other => Err(invalid(path, other)),
+    }
+}
+
+fn text_align(path: &str, value: &Value) -> Result<lv_text_align_t, StyleSheetError> {
+    match string(path, value)? {
+        "auto" => Ok(lightvgl_sys::lv_text_align_t_LV_TEXT_ALIGN_AUTO),
+        "left" => Ok(lightvgl_sys::lv_text_align_t_LV_TEXT_ALIGN_LEFT),
+        "center" => Ok(lightvgl_sys::lv_text_align_t_LV_TEXT_ALIGN_CENTER),
+        "right" => Ok(lightvgl_sys::lv_text_align_t_LV_TEXT_ALIGN_RIGHT),
+        other => Err(invalid(path, other)),
+    }
+}
+
+fn parse_selector(path: &str, text: &str) -> Result<Selector, StyleSheetError> {
+    let mut part = Part::Main;
+    let mut state = State::DEFAULT;
+    for token in text.split('|').map(str::trim) {
+        match token {
+            "main" => part = Part::Main,
+            "scrollbar" => part = Part::Scrollbar,
+            "indicator" => part = Part::Indicator,
+            "knob" => part = Part::Knob,
+            "selected" => part = Part::Selected,
+            "items" => part = Part::Items,
+            "cursor" => part = Part::Cursor,
+            "any_part" => part = Part::Any,
+            "default" => {}
+            "checked" => state |= State::CHECKED,
+            "focused" => state |= State::FOCUSED,
+            "focus_key" => state |= State::FOCUS_KEY,
+            "edited" => state |= State::EDITED,
+            "hovered" => state |= State::HOVERED,
+            "pressed" => state |= State::PRESSED,
+            "scrolled" => state |= State::SCROLLED,
+            "disabled" => state |= State::DISABLED,
+            "user_1" => state |= State::USER_1,
+            "user_2" => state |= State::USER_2,
+            "user_3" => state |= State::USER_3,
+            "user_4" => state |= State::USER_4,
+            "any_state" => state |= State::ANY,
+            _ => return Err(invalid(path, token)),
+        }
+    }
+    Ok(Selector::new(part, state))
+}
@@ -1,13 +1,13 @@
 //! # Styles
 //!
 //! Styles are components that need to be added to entities.
-//! Right now a [`Style`] can only be applied to a single widget, but they are cloneable.
+//! A [`Style`] can only be applied to a single widget, but they are cloneable.
+//! To apply the same style to many widgets, wrap it in a [`StyleHandle`].
 //!
 //! ```rust
 //! # use lv_bevy_ecs::functions::*;
-//! # use lv_bevy_ecs::styles::Style;
+//! # use lv_bevy_ecs::styles::{Part, Style};
 //! # use lv_bevy_ecs::support::OpacityLevel;
-//! # use lv_bevy_ecs::sys::lv_part_t_LV_PART_MAIN;
 //! # use lv_bevy_ecs::widgets::*;
 //! #
 //! # lv_bevy_ecs::setup_test_display!();
@@ -23,15 +23,361 @@
 //!
 //! button_entity.insert(style);
 //! let widget = button_entity.get_mut::<Widget>().unwrap();
-//! assert_eq!(widget.get_style_opa_recursive(lv_part_t_LV_PART_MAIN), opacity - 1);
+//! assert_eq!(widget.get_style_opa_recursive(Part::Main), opacity - 1);
 //! ```
+//!
+//! ## Selectors
+//!
+//! A [`Selector`] is a [`Part`] combined with [`State`] flags with `|`, for example
+//! `Part::Indicator | State::CHECKED | State::PRESSED`. A single part or state can be passed
+//! wherever a selector is expected.
+//!
+//! ```rust
+//! # use lv_bevy_ecs::styles::{Part, Selector, State, Style};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! let pressed_knob = Part::Knob | State::PRESSED;
+//! assert_eq!(pressed_knob.part(), Some(Part::Knob));
+//! assert!(pressed_knob.state().contains(State::PRESSED));
+//!
+//! let style = Style::new(Part::Items | State::CHECKED);
+//! let mut slider = Slider::new();
+//! slider.set_style_bg_opa(128, pressed_knob);
+//! slider.add_state(State::PRESSED);
+//! assert_eq!(slider.get_style_bg_opa(Part::Knob), 128);
+//! ```
+//!
+//! ## Transitions
+//!
+//! A [`Transition`] animates the listed properties when the state of a widget changes,
+//! e.g. when a button is pressed.
+//!
+//! ```rust
+//! # use core::time::Duration;
+//! # use lv_bevy_ecs::animation::AnimPath;
+//! # use lv_bevy_ecs::styles::{Part, State, Style, StyleProp, Transition};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! # let mut world = LvglWorld::default();
+//! #
+//! let transition = Transition::builder(&[StyleProp::BgColor, StyleProp::TransformWidth])
+//!     .with_duration(Duration::from_millis(200))
+//!     .with_delay(Duration::from_millis(50))
+//!     .with_path(AnimPath::EaseOut)
+//!     .build();
+//!
+//! let mut pressed = Style::new(Part::Main | State::PRESSED);
+//! pressed.set_transform_width(4);
+//! pressed.set_transition(&transition);
+//! drop(transition);
+//!
+//! world.spawn((Button::new().into_inner(), pressed));
+//! ```
+//!
+//! ## Gradients
+//!
+//! A [`Gradient`] is built from [`Color`](crate::colors::Color) stops, at most
+//! `LV_GRADIENT_MAX_STOPS`. Linear, radial and conical gradients are available when
+//! `LV_USE_DRAW_SW_COMPLEX_GRADIENTS` is enabled.
+//!
+//! ```rust
+//! # use lv_bevy_ecs::colors::{Color, Palette};
+//! # use lv_bevy_ecs::styles::{Gradient, Part, Style};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! # let mut world = LvglWorld::default();
+//! #
+//! let gradient = Gradient::horizontal()
+//!     .with_stop(Palette::Green.main(), 0)
+//!     .with_stop(Color::hex(0xF44336), 255)
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut indicator = Style::new(Part::Indicator);
+//! indicator.set_bg_grad(&gradient);
+//!
+//! world.spawn((Bar::new().into_inner(), indicator));
+//! ```
+//!
+//! ## Style properties
+//!
+//! For one-off tweaks, style property components such as [`BgColor`], [`Radius`] or
+//! [`Padding`] set local style properties of the widget. They are applied when inserted and
+//! removed with the component. Changes are applied by the [`sync_style_properties`] system.
+//!
+//! ```rust
+//! # use lv_bevy_ecs::colors::Color;
+//! # use lv_bevy_ecs::styles::*;
+//! # use lv_bevy_ecs::support::Coord;
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! # let mut world = LvglWorld::default();
+//! #
+//! let button = world
+//!     .spawn((
+//!         Button::new().into_inner(),
+//!         BgColor::new(Color::hex(0x2196F3)),
+//!         Radius::new(12).with_selector(Part::Main | State::PRESSED),
+//!         Padding::all(8),
+//!     ))
+//!     .id();
+//!
+//! world.get_mut::<Padding>(button).unwrap().left = Coord::Px(16);
+//! world.run_system_cached(sync_style_properties).unwrap();
+//!
+//! let widget = world.get::<Widget>(button).unwrap();
+//! assert_eq!(widget.get_style_pad_left(Part::Main), 16);
+//! ```
+//!
+//! ## Shared styles
+//!
+//! A [`StyleHandle`] can be cloned and inserted into any number of entities, every clone refers
+//! to the same `lv_style_t`. Changes made with [`StyleHandle::modify()`] refresh all widgets using it.
+//!
+//! ```rust
+//! # use lv_bevy_ecs::styles::{Part, Style, StyleHandle};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! # let mut world = LvglWorld::default();
+//! #
+//! let mut style = Style::default();
+//! style.set_radius(4);
+//! let rounded = StyleHandle::new(style);
+//!
+//! let first = world.spawn((Button::new().into_inner(), rounded.clone())).id();
+//! let second = world.spawn((Button::new().into_inner(), rounded.clone())).id();
+//!
+//! rounded.modify(|style| style.set_radius(8));
+//! for entity in [first, second] {
+//!     let widget = world.get::<Widget>(entity).unwrap();
+//!     assert_eq!(widget.get_style_radius(Part::Main), 8);
+//! }
+//! ```
+
+use ::alloc::{boxed::Box, sync::Arc, vec::Vec};
+use ::core::{
+    ffi::c_void,
+    mem::MaybeUninit,
+    ops::{BitOr, BitOrAssign},
+    time::Duration,
+};
+
+use bevy_ecs::{
+    component::{Component, Mutable},
+    lifecycle::HookContext,
+    query::Changed,
+    system::{ParamSet, Query},
+    world::{DeferredWorld, World},
+};
+use bevy_platform::sync::{PoisonError, RwLock, RwLockReadGuard};
+use lightvgl_sys::{
+    lv_color_t, lv_grad_dsc_t, lv_grad_extend_t, lv_obj_t, lv_opa_t, lv_part_t, lv_state_t,
+    lv_style_prop_t, lv_style_selector_t, lv_style_transition_dsc_t,
+};
+use thiserror::Error;
+
+#[cfg(LV_USE_FLEX)]
+use crate::flex::{FlexItem, FlexLayout};
+#[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+use crate::support::Coord;
+use crate::{
+    animation::AnimPath,
+    colors::Color,
+    widgets::{RawObj, Widget},
+};
+
+/// The part of a widget a style applies to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum Part {
+    /// The background
+    #[default]
+    Main = lightvgl_sys::lv_part_t_LV_PART_MAIN,
+    Scrollbar = lightvgl_sys::lv_part_t_LV_PART_SCROLLBAR,
+    /// E.g. the filled area of a slider
+    Indicator = lightvgl_sys::lv_part_t_LV_PART_INDICATOR,
+    /// The handle of a slider or an arc
+    Knob = lightvgl_sys::lv_part_t_LV_PART_KNOB,
+    /// The selected item or text
+    Selected = lightvgl_sys::lv_part_t_LV_PART_SELECTED,
+    /// The items of e.g. a button matrix
+    Items = lightvgl_sys::lv_part_t_LV_PART_ITEMS,
+    /// The text cursor
+    Cursor = lightvgl_sys::lv_part_t_LV_PART_CURSOR,
+    /// The first part available to custom widgets
+    CustomFirst = lightvgl_sys::lv_part_t_LV_PART_CUSTOM_FIRST,
+    /// Every part
+    Any = lightvgl_sys::lv_part_t_LV_PART_ANY,
+}
+
+impl Part {
+    const ALL: [Self; 9] = [
+        Self::Main,
+        Self::Scrollbar,
+        Self::Indicator,
+        Self::Knob,
+        Self::Selected,
+        Self::Items,
+        Self::Cursor,
+        Self::CustomFirst,
+        Self::Any,
+    ];
+}
+
+impl From<Part> for lv_part_t {
+    #[inline]
+    fn from(value: Part) -> Self {
+        value as Self
+    }
+}
+
+/// State flags of a widget, combine them with `|`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct State(lv_state_t);
+
+impl State {
+    pub const DEFAULT: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_DEFAULT);
+    pub const CHECKED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_CHECKED);
+    pub const FOCUSED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_FOCUSED);
+    /// Focused with a keypad or an encoder
+    pub const FOCUS_KEY: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_FOCUS_KEY);
+    /// Edited with an encoder
+    pub const EDITED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_EDITED);
+    pub const HOVERED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_HOVERED);
+    pub const PRESSED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_PRESSED);
+    pub const SCROLLED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_SCROLLED);
+    pub const DISABLED: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_DISABLED);
+    pub const USER_1: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_USER_1);
+    pub const USER_2: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_USER_2);
+    pub const USER_3: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_USER_3);
+    pub const USER_4: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_USER_4);
+    /// Matches every state
+    pub const ANY: Self = Self(lightvgl_sys::lv_state_t_LV_STATE_ANY);
+
+    #[inline]
+    pub const fn from_bits(bits: lv_state_t) -> Self {
+        Self(bits)
+    }
+
+    #[inline]
+    pub const fn bits(self) -> lv_state_t {
+        self.0
+    }
+
+    /// Returns whether every flag of `other` is set
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for State {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for State {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl From<State> for lv_state_t {
+    #[inline]
+    fn from(value: State) -> Self {
+        value.0
+    }
+}
+
+/// A [`Part`] and [`State`] flags, created with `Part::Knob | State::PRESSED`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Selector(lv_style_selector_t);
+
+impl Selector {
+    #[inline]
+    pub const fn new(part: Part, state: State) -> Self {
+        Self(part as lv_style_selector_t | state.0 as lv_style_selector_t)
+    }
+
+    #[inline]
+    pub const fn bits(self) -> lv_style_selector_t {
+        self.0
+    }
+
+    /// Returns the part, or `None` for a custom part after [`Part::CustomFirst`]
+    #[inline]
+    pub fn part(self) -> Option<Part> {
+        let part = self.0 & lightvgl_sys::lv_part_t_LV_PART_ANY as lv_style_selector_t;
+        Part::ALL
+            .into_iter()
+            .find(|candidate| *candidate as lv_style_selector_t == part)
+    }
+
+    #[inline]
+    pub const fn state(self) -> State {
+        State((self.0 & lightvgl_sys::lv_state_t_LV_STATE_ANY as lv_style_selector_t) as lv_state_t)
+    }
+}
+
+impl From<Part> for Selector {
+    #[inline]
+    fn from(value: Part) -> Self {
+        Self::new(value, State::DEFAULT)
+    }
+}
 
-use ::core::mem::MaybeUninit;
+impl From<State> for Selector {
+    #[inline]
+    fn from(value: State) -> Self {
+        Self::new(Part::Main, value)
+    }
+}
 
-use bevy_ecs::{component::Component, lifecycle::HookContext, world::DeferredWorld};
-use lightvgl_sys::{lv_part_t_LV_PART_MAIN, lv_style_selector_t};
+impl From<Selector> for lv_style_selector_t {
+    #[inline]
+    fn from(value: Selector) -> Self {
+        value.0
+    }
+}
 
-use crate::widgets::{RawObj, Widget};
+impl BitOr<State> for Part {
+    type Output = Selector;
+    #[inline]
+    fn bitor(self, rhs: State) -> Self::Output {
+        Selector::new(self, rhs)
+    }
+}
+
+impl BitOr<Part> for State {
+    type Output = Selector;
+    #[inline]
+    fn bitor(self, rhs: Part) -> Self::Output {
+        Selector::new(rhs, self)
+    }
+}
+
+impl BitOr<State> for Selector {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: State) -> Self::Output {
+        Self(self.0 | rhs.0 as lv_style_selector_t)
+    }
+}
 
 #[derive(Component)]
 #[component(on_insert=add_style)]
@@ -39,7 +385,9 @@ use crate::widgets::{RawObj, Widget};
 #[component(storage = "SparseSet")] // TODO investigate why styles don't get applied without this
 pub struct Style {
     raw: lightvgl_sys::lv_style_t,
-    selector: lv_style_selector_t,
+    selector: Selector,
+    transition: Option<Transition>,
+    gradient: Option<Gradient>,
 }
 
 impl Default for Style {
@@ -51,7 +399,9 @@ impl Default for Style {
         };
         Self {
             raw,
-            selector: lv_part_t_LV_PART_MAIN as lv_style_selector_t,
+            selector: Selector::default(),
+            transition: None,
+            gradient: None,
         }
     }
 }
@@ -62,23 +412,57 @@ impl Clone for Style {
         //lv_style_copy(&mut result, self);
         result.copy(self);
         result.selector = self.selector;
+        result.transition.clone_from(&self.transition);
+        result.gradient.clone_from(&self.gradient);
         result
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.copy(source);
         self.selector = source.selector;
+        self.transition.clone_from(&source.transition);
+        self.gradient.clone_from(&source.gradient);
     }
 }
 
 impl Style {
-    pub fn new(selector: lv_style_selector_t) -> Self {
+    pub fn new(selector: impl Into<Selector>) -> Self {
         let raw = unsafe {
             let mut style = MaybeUninit::<lightvgl_sys::lv_style_t>::uninit();
             lightvgl_sys::lv_style_init(style.as_mut_ptr());
             style.assume_init()
         };
-        Self { raw, selector }
+        Self {
+            raw,
+            selector: selector.into(),
+            transition: None,
+            gradient: None,
+        }
+    }
+
+    /// Animates the changes of the transition's properties when the state of a widget changes
+    ///
+    /// The style keeps a reference to the transition, so it can be dropped afterwards.
+    pub fn set_transition(&mut self, transition: &Transition) {
+        unsafe {
+            lightvgl_sys::lv_style_set_transition(&mut self.raw, transition.raw());
+        }
+        self.transition = Some(transition.clone());
+    }
+
+    /// Sets the background gradient, the colors of the gradient override `bg_grad_color`
+    ///
+    /// The style keeps a reference to the gradient, so it can be dropped afterwards.
+    pub fn set_bg_grad(&mut self, gradient: &Gradient) {
+        unsafe {
+            lightvgl_sys::lv_style_set_bg_grad(&mut self.raw, gradient.raw());
+        }
+        self.gradient = Some(gradient.clone());
+    }
+
+    #[inline]
+    pub fn selector(&self) -> Selector {
+        self.selector
     }
 
     #[inline]
@@ -95,6 +479,10 @@ impl Style {
 impl Drop for Style {
     fn drop(&mut self) {
         crate::info!("Dropping Style");
+        // the hooks have already removed the style from its widgets, so its values can be freed
+        unsafe {
+            lightvgl_sys::lv_style_reset(&mut self.raw);
+        }
     }
 }
 
@@ -108,19 +496,817 @@ fn add_style(mut world: DeferredWorld, ctx: HookContext) {
         .raw_mut();
     let style = world.get_mut::<Style>(ctx.entity).unwrap();
     unsafe {
-        lightvgl_sys::lv_obj_add_style(widget, &style.raw, style.selector);
+        lightvgl_sys::lv_obj_add_style(widget, &style.raw, style.selector.bits());
     }
     crate::info!("Added Style");
 }
 
 fn remove_style(mut world: DeferredWorld, ctx: HookContext) {
+    // the widget may have been removed first
+    let Some(mut widget) = world.get_mut::<Widget>(ctx.entity) else {
+        return;
+    };
+    let widget = widget.raw_mut();
+    let style = world.get_mut::<Style>(ctx.entity).unwrap();
+    unsafe {
+        lightvgl_sys::lv_obj_remove_style(widget, &style.raw, style.selector.bits());
+    }
+    crate::info!("Removed Style");
+}
+
+/// A reference counted [`Style`] that can be inserted into many entities
+///
+/// Every clone refers to the same `lv_style_t`, it is freed when the last clone is dropped.
+#[derive(Component, Clone)]
+#[component(on_insert=add_style_handle)]
+#[component(on_discard=remove_style_handle)]
+#[component(storage = "SparseSet")]
+pub struct StyleHandle {
+    style: Arc<RwLock<Style>>,
+}
+
+impl StyleHandle {
+    #[inline]
+    pub fn new(style: Style) -> Self {
+        Self {
+            style: Arc::new(RwLock::new(style)),
+        }
+    }
+
+    /// Locks the shared style for reading
+    ///
+    /// It blocks while the style is being modified, calling it from [`modify()`](StyleHandle::modify)
+    /// deadlocks.
+    #[inline]
+    pub fn style(&self) -> RwLockReadGuard<'_, Style> {
+        self.style.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Modifies the shared style and refreshes every widget that uses it
+    ///
+    /// It blocks while the style is locked by [`style()`](StyleHandle::style).
+    pub fn modify<R>(&self, f: impl FnOnce(&mut Style) -> R) -> R {
+        let mut style = self.style.write().unwrap_or_else(PoisonError::into_inner);
+        let result = f(&mut style);
+        unsafe {
+            lightvgl_sys::lv_obj_report_style_change(style.raw_mut());
+        }
+        result
+    }
+
+    /// Returns whether the two handles refer to the same style
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.style, &other.style)
+    }
+
+    pub(crate) fn raw_and_selector(
+        &self,
+    ) -> (*const lightvgl_sys::lv_style_t, lv_style_selector_t) {
+        let style = self.style();
+        (style.raw(), style.selector.bits())
+    }
+}
+
+impl From<Style> for StyleHandle {
+    #[inline]
+    fn from(style: Style) -> Self {
+        Self::new(style)
+    }
+}
+
+fn add_style_handle(mut world: DeferredWorld, ctx: HookContext) {
     let widget = world
         .get_mut::<Widget>(ctx.entity)
-        .expect("Style components must be added to Widget entities")
+        .expect("StyleHandle components must be added to Widget entities")
         .raw_mut();
-    let style = world.get_mut::<Style>(ctx.entity).unwrap();
+    let (style, selector) = world
+        .get::<StyleHandle>(ctx.entity)
+        .unwrap()
+        .raw_and_selector();
     unsafe {
-        lightvgl_sys::lv_obj_remove_style(widget, &style.raw, style.selector);
+        lightvgl_sys::lv_obj_add_style(widget, style, selector);
+    }
+    crate::info!("Added StyleHandle");
+}
+
+fn remove_style_handle(mut world: DeferredWorld, ctx: HookContext) {
+    // the widget may have been removed first
+    let Some(mut widget) = world.get_mut::<Widget>(ctx.entity) else {
+        return;
+    };
+    let widget = widget.raw_mut();
+    let (style, selector) = world
+        .get::<StyleHandle>(ctx.entity)
+        .unwrap()
+        .raw_and_selector();
+    unsafe {
+        lightvgl_sys::lv_obj_remove_style(widget, style, selector);
+    }
+    crate::info!("Removed StyleHandle");
+}
+
+/// Style property identifiers, e.g. for [`Transition`]s
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StyleProp {
+    Width = lightvgl_sys::_lv_style_id_t_LV_STYLE_WIDTH as u8,
+    Height = lightvgl_sys::_lv_style_id_t_LV_STYLE_HEIGHT as u8,
+    X = lightvgl_sys::_lv_style_id_t_LV_STYLE_X as u8,
+    Y = lightvgl_sys::_lv_style_id_t_LV_STYLE_Y as u8,
+    PadTop = lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_TOP as u8,
+    PadBottom = lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_BOTTOM as u8,
+    PadLeft = lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_LEFT as u8,
+    PadRight = lightvgl_sys::_lv_style_id_t_LV_STYLE_PAD_RIGHT as u8,
+    Radius = lightvgl_sys::_lv_style_id_t_LV_STYLE_RADIUS as u8,
+    BgColor = lightvgl_sys::_lv_style_id_t_LV_STYLE_BG_COLOR as u8,
+    BgOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_BG_OPA as u8,
+    BorderColor = lightvgl_sys::_lv_style_id_t_LV_STYLE_BORDER_COLOR as u8,
+    BorderOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_BORDER_OPA as u8,
+    BorderWidth = lightvgl_sys::_lv_style_id_t_LV_STYLE_BORDER_WIDTH as u8,
+    OutlineWidth = lightvgl_sys::_lv_style_id_t_LV_STYLE_OUTLINE_WIDTH as u8,
+    OutlineColor = lightvgl_sys::_lv_style_id_t_LV_STYLE_OUTLINE_COLOR as u8,
+    OutlineOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_OUTLINE_OPA as u8,
+    ShadowWidth = lightvgl_sys::_lv_style_id_t_LV_STYLE_SHADOW_WIDTH as u8,
+    ShadowOffsetX = lightvgl_sys::_lv_style_id_t_LV_STYLE_SHADOW_OFFSET_X as u8,
+    ShadowOffsetY = lightvgl_sys::_lv_style_id_t_LV_STYLE_SHADOW_OFFSET_Y as u8,
+    ShadowColor = lightvgl_sys::_lv_style_id_t_LV_STYLE_SHADOW_COLOR as u8,
+    ShadowOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_SHADOW_OPA as u8,
+    ImageOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_IMAGE_OPA as u8,
+    ImageRecolor = lightvgl_sys::_lv_style_id_t_LV_STYLE_IMAGE_RECOLOR as u8,
+    ImageRecolorOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_IMAGE_RECOLOR_OPA as u8,
+    TextColor = lightvgl_sys::_lv_style_id_t_LV_STYLE_TEXT_COLOR as u8,
+    TextOpa = lightvgl_sys::_lv_style_id_t_LV_STYLE_TEXT_OPA as u8,
+    Opa = lightvgl_sys::_lv_style_id_t_LV_STYLE_OPA as u8,
+    TransformWidth = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSFORM_WIDTH as u8,
+    TransformHeight = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSFORM_HEIGHT as u8,
+    TranslateX = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSLATE_X as u8,
+    TranslateY = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSLATE_Y as u8,
+    TransformScaleX = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSFORM_SCALE_X as u8,
+    TransformScaleY = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSFORM_SCALE_Y as u8,
+    TransformRotation = lightvgl_sys::_lv_style_id_t_LV_STYLE_TRANSFORM_ROTATION as u8,
+}
+
+impl From<StyleProp> for lv_style_prop_t {
+    #[inline]
+    fn from(value: StyleProp) -> Self {
+        value as Self
+    }
+}
+
+/// A shared `lv_style_transition_dsc_t`, created with [`Transition::builder()`]
+///
+/// [`Style::set_transition()`] keeps a clone, so the descriptor lives as long as the styles using it.
+#[derive(Clone)]
+pub struct Transition {
+    inner: Arc<TransitionInner>,
+}
+
+struct TransitionInner {
+    dsc: lv_style_transition_dsc_t,
+    /// Referenced by `dsc`, terminated with `LV_STYLE_PROP_INV`
+    _props: Box<[lv_style_prop_t]>,
+}
+
+impl Transition {
+    #[inline]
+    pub fn builder(props: &[StyleProp]) -> TransitionBuilder {
+        TransitionBuilder::new(props)
+    }
+
+    #[inline]
+    pub fn raw(&self) -> *const lv_style_transition_dsc_t {
+        &self.inner.dsc
+    }
+}
+
+// SAFETY: the descriptor and the property list it points to are owned by the `Arc` and never
+// written after `TransitionBuilder::build()`, LVGL only reads them. The path callback is a plain
+// function and the user data is null, so the handle can be moved and shared like plain data.
+unsafe impl Send for Transition {}
+unsafe impl Sync for Transition {}
+
+pub struct TransitionBuilder {
+    props: Vec<lv_style_prop_t>,
+    duration: Duration,
+    delay: Duration,
+    path: AnimPath,
+}
+
+impl TransitionBuilder {
+    /// Transition of `props` that takes 0 ms, use [`with_duration()`](Self::with_duration)
+    pub fn new(props: &[StyleProp]) -> Self {
+        Self {
+            props: props.iter().map(|&prop| prop.into()).collect(),
+            duration: Duration::ZERO,
+            delay: Duration::ZERO,
+            path: AnimPath::Linear,
+        }
+    }
+
+    #[inline]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    #[inline]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    #[inline]
+    pub fn with_path(mut self, path: AnimPath) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn build(mut self) -> Transition {
+        self.props
+            .push(lightvgl_sys::_lv_style_id_t_LV_STYLE_PROP_INV as lv_style_prop_t);
+        let props = self.props.into_boxed_slice();
+        let dsc = unsafe {
+            let mut dsc = MaybeUninit::<lv_style_transition_dsc_t>::uninit();
+            lightvgl_sys::lv_style_transition_dsc_init(
+                dsc.as_mut_ptr(),
+                props.as_ptr(),
+                self.path.into(),
+                self.duration.as_millis().try_into().unwrap_or(u32::MAX),
+                self.delay.as_millis().try_into().unwrap_or(u32::MAX),
+                ::core::ptr::null_mut::<c_void>(),
+            );
+            dsc.assume_init()
+        };
+        Transition {
+            inner: Arc::new(TransitionInner { dsc, _props: props }),
+        }
+    }
+}
+
+/// How a gradient continues outside of its start and end points
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum GradientExtend {
+    /// Repeats the color of the first and last stop
+    #[default]
+    Pad = lightvgl_sys::lv_grad_extend_t_LV_GRAD_EXTEND_PAD,
+    Repeat = lightvgl_sys::lv_grad_extend_t_LV_GRAD_EXTEND_REPEAT,
+    Reflect = lightvgl_sys::lv_grad_extend_t_LV_GRAD_EXTEND_REFLECT,
+}
+
+impl From<GradientExtend> for lv_grad_extend_t {
+    #[inline]
+    fn from(value: GradientExtend) -> Self {
+        value as Self
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GradientError {
+    #[error("a gradient needs at least 2 stops, found {0}")]
+    TooFewStops(usize),
+    #[error("at most {max} stops are allowed (LV_GRADIENT_MAX_STOPS), found {found}")]
+    TooManyStops { max: usize, found: usize },
+}
+
+/// A shared `lv_grad_dsc_t`, created with [`Gradient::horizontal()`], [`Gradient::vertical()`]
+/// or with the complex gradient constructors
+///
+/// [`Style::set_bg_grad()`] keeps a clone, so the descriptor lives as long as the styles using it.
+#[derive(Clone)]
+pub struct Gradient {
+    inner: Arc<lv_grad_dsc_t>,
+}
+
+impl Gradient {
+    #[inline]
+    pub fn horizontal() -> GradientBuilder {
+        GradientBuilder::new(GradientKind::Horizontal)
+    }
+
+    #[inline]
+    pub fn vertical() -> GradientBuilder {
+        GradientBuilder::new(GradientKind::Vertical)
+    }
+
+    /// Linear gradient from the start to the end point, relative to the widget
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    #[inline]
+    pub fn linear(
+        from: (impl Into<Coord>, impl Into<Coord>),
+        to: (impl Into<Coord>, impl Into<Coord>),
+    ) -> GradientBuilder {
+        GradientBuilder::new(GradientKind::Linear {
+            from: (raw_coord(from.0), raw_coord(from.1)),
+            to: (raw_coord(to.0), raw_coord(to.1)),
+        })
+    }
+
+    /// Radial gradient from the center to the circle going through `to`
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    #[inline]
+    pub fn radial(
+        center: (impl Into<Coord>, impl Into<Coord>),
+        to: (impl Into<Coord>, impl Into<Coord>),
+    ) -> GradientBuilder {
+        GradientBuilder::new(GradientKind::Radial {
+            center: (raw_coord(center.0), raw_coord(center.1)),
+            to: (raw_coord(to.0), raw_coord(to.1)),
+            focal: None,
+        })
+    }
+
+    /// Conical gradient around the center between two angles in degrees
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    #[inline]
+    pub fn conical(
+        center: (impl Into<Coord>, impl Into<Coord>),
+        start_angle: i32,
+        end_angle: i32,
+    ) -> GradientBuilder {
+        GradientBuilder::new(GradientKind::Conical {
+            center: (raw_coord(center.0), raw_coord(center.1)),
+            angles: (start_angle, end_angle),
+        })
+    }
+
+    #[inline]
+    pub fn raw(&self) -> *const lv_grad_dsc_t {
+        &*self.inner
+    }
+}
+
+// SAFETY: Rust never touches the descriptor after it is built, only its address is handed out.
+// LVGL may keep its draw state in the descriptor while rendering, which only happens on the thread
+// that runs `lv_timer_handler()`. Cloning or dropping the handle only touches the `Arc`.
+unsafe impl Send for Gradient {}
+unsafe impl Sync for Gradient {}
+
+#[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+type RawPoint = (lightvgl_sys::lv_coord_t, lightvgl_sys::lv_coord_t);
+
+#[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+fn raw_coord(value: impl Into<Coord>) -> lightvgl_sys::lv_coord_t {
+    Into::<Coord>::into(value).into()
+}
+
+enum GradientKind {
+    Horizontal,
+    Vertical,
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    Linear {
+        from: RawPoint,
+        to: RawPoint,
+    },
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    Radial {
+        center: RawPoint,
+        to: RawPoint,
+        /// Center and radius of the start circle
+        focal: Option<(RawPoint, lightvgl_sys::lv_coord_t)>,
+    },
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    Conical {
+        center: RawPoint,
+        angles: (i32, i32),
+    },
+}
+
+pub struct GradientBuilder {
+    kind: GradientKind,
+    colors: Vec<lv_color_t>,
+    opas: Vec<lv_opa_t>,
+    fracs: Vec<u8>,
+    extend: GradientExtend,
+}
+
+impl GradientBuilder {
+    fn new(kind: GradientKind) -> Self {
+        Self {
+            kind,
+            colors: Vec::new(),
+            opas: Vec::new(),
+            fracs: Vec::new(),
+            extend: GradientExtend::default(),
+        }
+    }
+
+    /// Adds an opaque color stop, `frac` 0 is the start and 255 is the end of the gradient
+    #[inline]
+    pub fn with_stop(self, color: impl Into<Color>, frac: u8) -> Self {
+        self.with_stop_opa(
+            color,
+            lightvgl_sys::_lv_opacity_level_t_LV_OPA_COVER as lv_opa_t,
+            frac,
+        )
+    }
+
+    /// Adds a color stop with opacity, `frac` 0 is the start and 255 is the end of the gradient
+    pub fn with_stop_opa(mut self, color: impl Into<Color>, opa: lv_opa_t, frac: u8) -> Self {
+        self.colors.push(Into::<Color>::into(color).into());
+        self.opas.push(opa);
+        self.fracs.push(frac);
+        self
+    }
+
+    /// Only used by complex gradients
+    #[inline]
+    pub fn with_extend(mut self, extend: GradientExtend) -> Self {
+        self.extend = extend;
+        self
+    }
+
+    /// Moves the start circle of a radial gradient
+    #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+    pub fn with_focal(
+        mut self,
+        center: (impl Into<Coord>, impl Into<Coord>),
+        radius: impl Into<Coord>,
+    ) -> Self {
+        if let GradientKind::Radial { focal, .. } = &mut self.kind {
+            *focal = Some((
+                (raw_coord(center.0), raw_coord(center.1)),
+                raw_coord(radius),
+            ));
+        }
+        self
+    }
+
+    pub fn build(self) -> Result<Gradient, GradientError> {
+        let found = self.colors.len();
+        let max = lightvgl_sys::LV_GRADIENT_MAX_STOPS as usize;
+        if found < 2 {
+            return Err(GradientError::TooFewStops(found));
+        }
+        if found > max {
+            return Err(GradientError::TooManyStops { max, found });
+        }
+        let dsc = unsafe {
+            let mut dsc = MaybeUninit::<lv_grad_dsc_t>::zeroed();
+            let raw = dsc.as_mut_ptr();
+            lightvgl_sys::lv_grad_init_stops(
+                raw,
+                self.colors.as_ptr(),
+                self.opas.as_ptr(),
+                self.fracs.as_ptr(),
+                found as i32,
+            );
+            let extend = self.extend.into();
+            match self.kind {
+                GradientKind::Horizontal => lightvgl_sys::lv_grad_horizontal_init(raw),
+                GradientKind::Vertical => lightvgl_sys::lv_grad_vertical_init(raw),
+                #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+                GradientKind::Linear { from, to } => {
+                    lightvgl_sys::lv_grad_linear_init(raw, from.0, from.1, to.0, to.1, extend);
+                }
+                #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+                GradientKind::Radial { center, to, focal } => {
+                    lightvgl_sys::lv_grad_radial_init(raw, center.0, center.1, to.0, to.1, extend);
+                    if let Some(((x, y), radius)) = focal {
+                        lightvgl_sys::lv_grad_radial_set_focal(raw, x, y, radius);
+                    }
+                }
+                #[cfg(LV_USE_DRAW_SW_COMPLEX_GRADIENTS)]
+                GradientKind::Conical { center, angles } => {
+                    lightvgl_sys::lv_grad_conical_init(
+                        raw, center.0, center.1, angles.0, angles.1, extend,
+                    );
+                }
+            }
+            // the horizontal and vertical init functions don't set it
+            (*raw).set_extend(extend);
+            dsc.assume_init()
+        };
+        Ok(Gradient {
+            inner: Arc::new(dsc),
+        })
+    }
+}
+
+pub(crate) trait StyleProperty: Component<Mutability = Mutable> {
+    /// Sets the local style properties, the ones set with a previous selector are removed
+    fn apply(&mut self, obj: *mut lv_obj_t);
+    fn remove(&self, obj: *mut lv_obj_t);
+}
+
+/// Removes the local style properties `props` of `obj` that were set with `selector`
+fn remove_local_props(obj: *mut lv_obj_t, props: &[StyleProp], selector: Selector) {
+    for prop in props {
+        unsafe {
+            lightvgl_sys::lv_obj_remove_local_style_prop(obj, (*prop).into(), selector.bits());
+        }
+    }
+}
+
+/// Converts the field of a style property component to the type LVGL expects
+trait PropertyValue: Copy {
+    type Raw;
+    fn raw(self) -> Self::Raw;
+}
+
+impl PropertyValue for i32 {
+    type Raw = Self;
+    #[inline]
+    fn raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl PropertyValue for lv_opa_t {
+    type Raw = Self;
+    #[inline]
+    fn raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl PropertyValue for Color {
+    type Raw = lv_color_t;
+    #[inline]
+    fn raw(self) -> Self::Raw {
+        self.into()
+    }
+}
+
+macro_rules! style_property {
+    ($(#[$meta:meta])* $name:ident, $field:ident: $t:ty, $setter:ident, $prop:ident) => {
+        $(#[$meta])*
+        #[derive(Component, Debug, Copy, Clone)]
+        #[component(on_insert = apply_property::<$name>)]
+        #[component(on_discard = remove_property::<$name>)]
+        pub struct $name {
+            pub $field: $t,
+            pub selector: Selector,
+            /// The selector the property was last set with
+            applied: Option<Selector>,
+        }
+
+        impl $name {
+            #[inline]
+            pub fn new($field: impl Into<$t>) -> Self {
+                Self {
+                    $field: $field.into(),
+                    selector: Selector::default(),
+                    applied: None,
+                }
+            }
+
+            #[inline]
+            pub fn with_selector(mut self, selector: impl Into<Selector>) -> Self {
+                self.selector = selector.into();
+                self
+            }
+        }
+
+        impl StyleProperty for $name {
+            fn apply(&mut self, obj: *mut lv_obj_t) {
+                if let Some(applied) = self.applied.replace(self.selector)
+                    && applied != self.selector
+                {
+                    remove_local_props(obj, &[StyleProp::$prop], applied);
+                }
+                unsafe { lightvgl_sys::$setter(obj, self.$field.raw(), self.selector.bits()) }
+            }
+
+            fn remove(&self, obj: *mut lv_obj_t) {
+                if let Some(applied) = self.applied {
+                    remove_local_props(obj, &[StyleProp::$prop], applied);
+                }
+            }
+        }
+    };
+}
+
+style_property!(
+    /// Local background color
+    BgColor, color: Color, lv_obj_set_style_bg_color, BgColor
+);
+style_property!(
+    /// Local text color
+    TextColor, color: Color, lv_obj_set_style_text_color, TextColor
+);
+style_property!(
+    /// Local corner radius
+    Radius, radius: i32, lv_obj_set_style_radius, Radius
+);
+style_property!(
+    /// Local opacity of the widget and its children
+    Opacity, opa: lv_opa_t, lv_obj_set_style_opa, Opa
+);
+style_property!(
+    /// Local border width
+    BorderWidth, width: i32, lv_obj_set_style_border_width, BorderWidth
+);
+
+/// Local padding
+#[derive(Component, Debug, Copy, Clone)]
+#[component(on_insert = apply_property::<Padding>)]
+#[component(on_discard = remove_property::<Padding>)]
+pub struct Padding {
+    pub top: Coord,
+    pub bottom: Coord,
+    pub left: Coord,
+    pub right: Coord,
+    pub selector: Selector,
+    /// The selector the padding was last set with
+    applied: Option<Selector>,
+}
+
+const PADDING_PROPS: [StyleProp; 4] = [
+    StyleProp::PadTop,
+    StyleProp::PadBottom,
+    StyleProp::PadLeft,
+    StyleProp::PadRight,
+];
+
+impl Padding {
+    #[inline]
+    pub fn new(
+        top: impl Into<Coord>,
+        bottom: impl Into<Coord>,
+        left: impl Into<Coord>,
+        right: impl Into<Coord>,
+    ) -> Self {
+        Self {
+            top: top.into(),
+            bottom: bottom.into(),
+            left: left.into(),
+            right: right.into(),
+            selector: Selector::default(),
+            applied: None,
+        }
+    }
+
+    /// The same padding on every side
+    #[inline]
+    pub fn all(padding: impl Into<Coord>) -> Self {
+        let padding = padding.into();
+        Self::new(padding, padding, padding, padding)
+    }
+
+    /// `vertical` padding at the top and bottom, `horizontal` padding on the left and right
+    #[inline]
+    pub fn symmetric(vertical: impl Into<Coord>, horizontal: impl Into<Coord>) -> Self {
+        let vertical = vertical.into();
+        let horizontal = horizontal.into();
+        Self::new(vertical, vertical, horizontal, horizontal)
+    }
+
+    #[inline]
+    pub fn with_selector(mut self, selector: impl Into<Selector>) -> Self {
+        self.selector = selector.into();
+        self
+    }
+}
+
+impl PartialEq for Padding {
+    fn eq(&self, other: &Self) -> bool {
+        // the applied selector is not part of the value
+        self.top == other.top
+            && self.bottom == other.bottom
+            && self.left == other.left
+            && self.right == other.right
+            && self.selector == other.selector
+    }
+}
+
+impl Eq for Padding {}
+
+impl StyleProperty for Padding {
+    fn apply(&mut self, obj: *mut lv_obj_t) {
+        if let Some(applied) = self.applied.replace(self.selector)
+            && applied != self.selector
+        {
+            remove_local_props(obj, &PADDING_PROPS, applied);
+        }
+        let selector = self.selector.bits();
+        unsafe {
+            lightvgl_sys::lv_obj_set_style_pad_top(obj, self.top.into(), selector);
+            lightvgl_sys::lv_obj_set_style_pad_bottom(obj, self.bottom.into(), selector);
+            lightvgl_sys::lv_obj_set_style_pad_left(obj, self.left.into(), selector);
+            lightvgl_sys::lv_obj_set_style_pad_right(obj, self.right.into(), selector);
+        }
+    }
+
+    fn remove(&self, obj: *mut lv_obj_t) {
+        if let Some(applied) = self.applied {
+            remove_local_props(obj, &PADDING_PROPS, applied);
+        }
+    }
+}
+
+/// Applies the changed style property and [flex](crate::flex) components to their widgets
+///
+/// Add it to a schedule, or run it with `world.run_system_cached(sync_style_properties)`.
+/// The system has to be cached, otherwise every property would count as changed.
+#[expect(clippy::type_complexity)]
+pub fn sync_style_properties(
+    mut set: ParamSet<(
+        Query<(&mut Widget, &mut BgColor), Changed<BgColor>>,
+        Query<(&mut Widget, &mut TextColor), Changed<TextColor>>,
+        Query<(&mut Widget, &mut Padding), Changed<Padding>>,
+        Query<(&mut Widget, &mut Radius), Changed<Radius>>,
+        Query<(&mut Widget, &mut Opacity), Changed<Opacity>>,
+        Query<(&mut Widget, &mut BorderWidth), Changed<BorderWidth>>,
+        FlexQueries,
+    )>,
+) {
+    apply_changed(set.p0());
+    apply_changed(set.p1());
+    apply_changed(set.p2());
+    apply_changed(set.p3());
+    apply_changed(set.p4());
+    apply_changed(set.p5());
+    #[cfg(LV_USE_FLEX)]
+    {
+        let mut flex = set.p6();
+        apply_changed(flex.p0());
+        apply_changed(flex.p1());
+    }
+}
+
+/// The flex queries of [`sync_style_properties`], nothing without `LV_USE_FLEX`
+#[cfg(LV_USE_FLEX)]
+type FlexQueries = ParamSet<
+    'static,
+    'static,
+    (
+        Query<
+            'static,
+            'static,
+            (&'static mut Widget, &'static mut FlexLayout),
+            Changed<FlexLayout>,
+        >,
+        Query<'static, 'static, (&'static mut Widget, &'static mut FlexItem), Changed<FlexItem>>,
+    ),
+>;
+#[cfg(not(LV_USE_FLEX))]
+type FlexQueries = ();
+
+#[expect(clippy::needless_pass_by_value)]
+fn apply_changed<P: StyleProperty>(mut query: Query<(&mut Widget, &mut P), Changed<P>>) {
+    for (mut widget, mut property) in &mut query {
+        // only the applied selector is updated
+        property.bypass_change_detection().apply(widget.raw_mut());
+    }
+}
+
+pub(crate) fn apply_property<P: StyleProperty>(mut world: DeferredWorld, ctx: HookContext) {
+    let widget = world
+        .get_mut::<Widget>(ctx.entity)
+        .expect("Style property components must be added to Widget entities")
+        .raw_mut();
+    world
+        .get_mut::<P>(ctx.entity)
+        .unwrap()
+        .bypass_change_detection()
+        .apply(widget);
+}
+
+pub(crate) fn remove_property<P: StyleProperty>(mut world: DeferredWorld, ctx: HookContext) {
+    // the widget may have been removed first
+    let Some(mut widget) = world.get_mut::<Widget>(ctx.entity) else {
+        return;
+    };
+    let widget = widget.raw_mut();
+    world.get::<P>(ctx.entity).unwrap().remove(widget);
+}
+
+/// Adds the styles and style properties of every entity to its widget again,
+/// after `lv_theme_apply()` has removed them
+pub(crate) fn reattach_styles(world: &mut World) {
+    let mut styles = world.query::<(&mut Widget, &Style)>();
+    for (mut widget, style) in styles.iter_mut(world) {
+        unsafe {
+            lightvgl_sys::lv_obj_add_style(widget.raw_mut(), &style.raw, style.selector.bits());
+        }
+    }
+    let mut handles = world.query::<(&mut Widget, &StyleHandle)>();
+    for (mut widget, handle) in handles.iter_mut(world) {
+        let (style, selector) = handle.raw_and_selector();
+        unsafe {
+            lightvgl_sys::lv_obj_add_style(widget.raw_mut(), style, selector);
+        }
+    }
+    reapply_properties::<BgColor>(world);
+    reapply_properties::<TextColor>(world);
+    reapply_properties::<Padding>(world);
+    reapply_properties::<Radius>(world);
+    reapply_properties::<Opacity>(world);
+    reapply_properties::<BorderWidth>(world);
+    #[cfg(LV_USE_FLEX)]
+    {
+        reapply_properties::<FlexLayout>(world);
+        reapply_properties::<FlexItem>(world);
+    }
+}
+
+fn reapply_properties<P: StyleProperty>(world: &mut World) {
+    let mut properties = world.query::<(&mut Widget, &mut P)>();
+    for (mut widget, mut property) in properties.iter_mut(world) {
+        property.bypass_change_detection().apply(widget.raw_mut());
     }
-    crate::info!("Removed Style");
 }
@@ -6,9 +6,21 @@ use core::{
     ops::{Deref, DerefMut},
 };
 
-use embedded_graphics::pixelcolor::{BinaryColor, Gray8, Rgb565, Rgb888};
+use embedded_graphics::pixelcolor::{
+    BinaryColor, Gray8, PixelColor, Rgb565, Rgb888, RgbColor,
+    raw::{RawData, RawU16, RawU32},
+};
+use lightvgl_sys::lv_coord_t;
+
+#[deprecated(note = "use `Coord::Content` instead")]
+pub const LV_SIZE_CONTENT: u32 = SIZE_CONTENT as u32;
 
-pub const LV_SIZE_CONTENT: u32 = lightvgl_sys::LV_COORD_MAX | lightvgl_sys::LV_COORD_TYPE_SPEC;
+const SIZE_CONTENT: lv_coord_t =
+    (lightvgl_sys::LV_COORD_MAX | lightvgl_sys::LV_COORD_TYPE_SPEC) as i32;
+const COORD_TYPE_SPEC: lv_coord_t = lightvgl_sys::LV_COORD_TYPE_SPEC as i32;
+/// The two bits above `LV_COORD_MAX` that mark special coordinates
+const COORD_TYPE_MASK: lv_coord_t = COORD_TYPE_SPEC * 3;
+const PCT_POS_MAX: lv_coord_t = lightvgl_sys::LV_PCT_POS_MAX as i32;
 
 #[macro_export]
 macro_rules! cstr {
@@ -28,37 +40,272 @@ pub(crate) fn assert_lv_is_initialized() {
     }
 }
 
-pub trait LvglColorFormat {
+/// A pixel type that LVGL can render into
+///
+/// # Safety
+///
+/// The pixel memory rendered by LVGL is reinterpreted as `[Self]`, and the framebuffer of a
+/// [`HeadlessDisplay`](crate::display::HeadlessDisplay) as bytes. `Self` must have the memory
+/// layout of one pixel of [`as_lv_color_format_t()`](LvglColorFormat::as_lv_color_format_t)
+/// without padding bytes, and with at least 8 bits per pixel every bit pattern must be a valid
+/// `Self`.
+pub unsafe trait LvglColorFormat: PixelColor {
+    /// Number of bytes LVGL puts in front of the pixels, e.g. the palette of indexed formats
+    const PALETTE_SIZE: usize = 0;
+
     fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t;
+
+    /// Iterates over the pixels of a flushed area in row-major order
+    ///
+    /// `colors` is the pixel data reinterpreted as `[Self]` and `data` is the same memory as bytes,
+    /// each row starts `stride` bytes after the previous one.
+    /// Formats with less than 8 bits per pixel get an empty `colors` slice and must decode `data`.
+    #[inline]
+    fn iter_colors<'a>(
+        _colors: &'a [Self],
+        data: &'a [u8],
+        width: u32,
+        stride: u32,
+    ) -> impl Iterator<Item = Self> + 'a {
+        let row_size = width as usize * size_of::<Self>();
+        data.chunks(stride as usize).flat_map(move |row| {
+            row.get(..row_size)
+                .unwrap_or_default()
+                .chunks_exact(size_of::<Self>())
+                // same memory as `colors`, only skipping the padding at the end of the rows
+                .map(|pixel| unsafe { pixel.as_ptr().cast::<Self>().read_unaligned() })
+        })
+    }
 }
 
-impl LvglColorFormat for Rgb565 {
+unsafe impl LvglColorFormat for Rgb565 {
     #[inline]
     fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
         lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565
     }
 }
 
-impl LvglColorFormat for Rgb888 {
+unsafe impl LvglColorFormat for Rgb565Swapped {
+    #[inline]
+    fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
+        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565_SWAPPED
+    }
+}
+
+unsafe impl LvglColorFormat for Rgb888 {
     #[inline]
     fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
         lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB888
     }
 }
 
-impl LvglColorFormat for Gray8 {
+unsafe impl LvglColorFormat for Argb8888 {
+    #[inline]
+    fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
+        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_ARGB8888
+    }
+}
+
+unsafe impl LvglColorFormat for Xrgb8888 {
+    #[inline]
+    fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
+        lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_XRGB8888
+    }
+}
+
+unsafe impl LvglColorFormat for Gray8 {
     #[inline]
     fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
         lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_L8
     }
 }
 
-impl LvglColorFormat for BinaryColor {
+unsafe impl LvglColorFormat for BinaryColor {
+    /// Two `lv_color32_t` entries for the background and foreground colors
+    const PALETTE_SIZE: usize = 8;
+
     #[inline]
     fn as_lv_color_format_t() -> lightvgl_sys::lv_color_format_t {
-        crate::warn!("Monochrome buffers are not supported. Proceed with caution!");
         lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_I1
     }
+
+    /// Pixels are packed 8 per byte, most significant bit first, rows are padded to `stride` bytes
+    fn iter_colors<'a>(
+        _colors: &'a [Self],
+        data: &'a [u8],
+        width: u32,
+        stride: u32,
+    ) -> impl Iterator<Item = Self> + 'a {
+        let width = width as usize;
+        data.chunks(stride as usize).flat_map(move |row| {
+            (0..width).map(move |x| {
+                let mask = 0x80 >> (x % 8);
+                if row.get(x / 8).is_some_and(|byte| byte & mask != 0) {
+                    BinaryColor::On
+                } else {
+                    BinaryColor::Off
+                }
+            })
+        })
+    }
+}
+
+/// RGB565 color with its two bytes swapped, as expected by most SPI display controllers
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct Rgb565Swapped(u16);
+
+impl Rgb565Swapped {
+    /// Returns the bytes in the order they should be sent to the display
+    #[inline]
+    pub const fn to_bytes(self) -> [u8; 2] {
+        self.0.to_ne_bytes()
+    }
+}
+
+impl PixelColor for Rgb565Swapped {
+    type Raw = RawU16;
+}
+
+impl From<RawU16> for Rgb565Swapped {
+    /// Reads the raw value of a little-endian image
+    #[inline]
+    fn from(value: RawU16) -> Self {
+        Self(value.into_inner())
+    }
+}
+
+impl From<Rgb565> for Rgb565Swapped {
+    #[inline]
+    fn from(value: Rgb565) -> Self {
+        Self(RawU16::from(value).into_inner().swap_bytes())
+    }
+}
+
+impl From<Rgb565Swapped> for Rgb565 {
+    #[inline]
+    fn from(value: Rgb565Swapped) -> Self {
+        Rgb565::from(RawU16::new(value.0.swap_bytes()))
+    }
+}
+
+/// 32-bit color with alpha channel, with the same memory layout as `lv_color32_t`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct Argb8888 {
+    pub blue: u8,
+    pub green: u8,
+    pub red: u8,
+    pub alpha: u8,
+}
+
+impl Argb8888 {
+    #[inline]
+    pub const fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self {
+            blue,
+            green,
+            red,
+            alpha,
+        }
+    }
+}
+
+impl PixelColor for Argb8888 {
+    type Raw = RawU32;
+}
+
+impl From<RawU32> for Argb8888 {
+    /// Reads the raw value of a little-endian image
+    #[inline]
+    fn from(value: RawU32) -> Self {
+        let [blue, green, red, alpha] = value.into_inner().to_le_bytes();
+        Self::new(red, green, blue, alpha)
+    }
+}
+
+impl From<Rgb888> for Argb8888 {
+    #[inline]
+    fn from(value: Rgb888) -> Self {
+        Self::new(value.r(), value.g(), value.b(), u8::MAX)
+    }
+}
+
+impl From<Argb8888> for Rgb888 {
+    #[inline]
+    fn from(value: Argb8888) -> Self {
+        Rgb888::new(value.red, value.green, value.blue)
+    }
+}
+
+/// 32-bit color where the fourth byte is ignored, with the same memory layout as `lv_color32_t`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct Xrgb8888 {
+    pub blue: u8,
+    pub green: u8,
+    pub red: u8,
+    unused: u8,
+}
+
+impl Xrgb8888 {
+    #[inline]
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self {
+            blue,
+            green,
+            red,
+            unused: u8::MAX,
+        }
+    }
+}
+
+impl PixelColor for Xrgb8888 {
+    type Raw = RawU32;
+}
+
+impl From<RawU32> for Xrgb8888 {
+    /// Reads the raw value of a little-endian image
+    #[inline]
+    fn from(value: RawU32) -> Self {
+        let [blue, green, red, _] = value.into_inner().to_le_bytes();
+        Self::new(red, green, blue)
+    }
+}
+
+impl From<Rgb888> for Xrgb8888 {
+    #[inline]
+    fn from(value: Rgb888) -> Self {
+        Self::new(value.r(), value.g(), value.b())
+    }
+}
+
+impl From<Xrgb8888> for Rgb888 {
+    #[inline]
+    fn from(value: Xrgb8888) -> Self {
+        Rgb888::new(value.red, value.green, value.blue)
+    }
+}
+
+/// Pixel formats that LVGL can render into
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(windows, repr(i32))]
+#[cfg_attr(not(windows), repr(u32))]
+pub enum ColorFormat {
+    I1 = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_I1,
+    L8 = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_L8,
+    Rgb565 = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565,
+    Rgb565Swapped = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB565_SWAPPED,
+    Rgb888 = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_RGB888,
+    Argb8888 = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_ARGB8888,
+    Xrgb8888 = lightvgl_sys::lv_color_format_t_LV_COLOR_FORMAT_XRGB8888,
+}
+
+impl From<ColorFormat> for lightvgl_sys::lv_color_format_t {
+    #[inline]
+    fn from(value: ColorFormat) -> Self {
+        value as lightvgl_sys::lv_color_format_t
+    }
 }
 
 /// Possible LVGL alignments for widgets.
@@ -135,6 +382,74 @@ impl From<TextAlign> for lightvgl_sys::lv_align_t {
     }
 }
 
+/// A position or a size that LVGL encodes into a single `lv_coord_t`
+///
+/// Integers convert to `Coord` by decoding them, so the values returned by getters such as
+/// `get_style_width()` can be matched with `Coord::from(value)`.
+///
+/// ```
+/// # use lv_bevy_ecs::styles::Part;
+/// # use lv_bevy_ecs::support::Coord;
+/// # use lv_bevy_ecs::widgets::*;
+/// #
+/// # lv_bevy_ecs::setup_test_display!();
+/// #
+/// let mut button = Button::new();
+/// button.set_size(Coord::Pct(50), Coord::Content);
+/// assert_eq!(Coord::from(button.get_style_width(Part::Main)), Coord::Pct(50));
+/// assert_eq!(Coord::from(button.get_style_height(Part::Main)), Coord::Content);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Coord {
+    /// Pixels
+    Px(i32),
+    /// Percent of the parent's size, between -1000 and 1000
+    Pct(i32),
+    /// Fits the children or the text, only valid for sizes
+    Content,
+    /// Density independent pixels, scaled by the DPI of the default display
+    Dpx(i32),
+}
+
+impl Coord {
+    /// Decodes a raw coordinate, density independent pixels are decoded as [`Coord::Px`]
+    pub const fn decode(raw: lv_coord_t) -> Self {
+        if raw & COORD_TYPE_MASK != COORD_TYPE_SPEC {
+            return Self::Px(raw);
+        }
+        if raw == SIZE_CONTENT {
+            return Self::Content;
+        }
+        let plain = raw & !COORD_TYPE_MASK;
+        if plain > 2 * PCT_POS_MAX {
+            Self::Px(raw)
+        } else if plain > PCT_POS_MAX {
+            Self::Pct(PCT_POS_MAX - plain)
+        } else {
+            Self::Pct(plain)
+        }
+    }
+}
+
+impl From<lv_coord_t> for Coord {
+    #[inline]
+    fn from(value: lv_coord_t) -> Self {
+        Self::decode(value)
+    }
+}
+
+impl From<Coord> for lv_coord_t {
+    #[inline]
+    fn from(value: Coord) -> Self {
+        match value {
+            Coord::Px(px) => px,
+            Coord::Pct(pct) => unsafe { lightvgl_sys::lv_pct(pct) },
+            Coord::Content => SIZE_CONTENT,
+            Coord::Dpx(dpx) => unsafe { lightvgl_sys::lv_dpx(dpx) },
+        }
+    }
+}
+
 #[cfg_attr(windows, repr(i32))]
 #[cfg_attr(not(windows), repr(u32))]
 pub enum LabelLongMode {
@@ -0,0 +1,275 @@
+//! # Themes
+//!
+//! A [`Theme`] adds styles to every new widget based on its class. Its apply callback can
+//! attach [`StyleHandle`]s, the theme keeps them alive. Themes can have a parent theme,
+//! e.g. the built-in default theme, that is applied first.
+//!
+//! ```
+//! # use lv_bevy_ecs::colors::Color;
+//! # use lv_bevy_ecs::display::Display;
+//! # use lv_bevy_ecs::functions::lv_color_hex;
+//! # use lv_bevy_ecs::styles::{Part, Style, StyleHandle};
+//! # use lv_bevy_ecs::themes::{Theme, reapply_theme};
+//! # use lv_bevy_ecs::widgets::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! fn make_theme(parent: Option<&Theme>, background: u32) -> Theme {
+//!     let mut style = Style::default();
+//!     style.set_bg_color(lv_color_hex(background));
+//!     let buttons = StyleHandle::new(style);
+//!
+//!     Theme::new(parent, move |widget, class| {
+//!         if class.is::<Button<Wdg>>() {
+//!             widget.add_style(&buttons);
+//!         }
+//!     })
+//! }
+//!
+//! let mut display = Display::get_default();
+//! let base = Theme::from_display(&display);
+//! let light = make_theme(base.as_ref(), 0xFFFFFF);
+//! let dark = make_theme(base.as_ref(), 0x202020);
+//!
+//! display.set_theme(&light);
+//! let mut world = LvglWorld::default();
+//! let button = world.spawn(Button::new().into_inner()).id();
+//! world.spawn(Widget::new()).add_child(button);
+//!
+//! let bg_color = |world: &LvglWorld| {
+//!     let button = world.get::<Widget>(button).unwrap();
+//!     Color::from(button.get_style_bg_color(Part::Main))
+//! };
+//! assert_eq!(bg_color(&world), Color::hex(0xFFFFFF));
+//!
+//! // switch at runtime
+//! display.set_theme(&dark);
+//! reapply_theme(&mut world);
+//! assert_eq!(bg_color(&world), Color::hex(0x202020));
+//! ```
+
+use ::alloc::{boxed::Box, sync::Arc, vec::Vec};
+use ::core::{
+    cell::RefCell,
+    ffi::CStr,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use bevy_ecs::world::World;
+use lightvgl_sys::{lv_obj_class_t, lv_obj_t, lv_theme_t};
+
+use crate::{
+    display::{Display, for_each_obj},
+    styles::StyleHandle,
+    widgets::{RawObj, Wdg, WidgetSpec},
+};
+
+/// A reference counted `lv_theme_t`
+#[derive(Clone)]
+pub struct Theme {
+    inner: Arc<ThemeInner>,
+}
+
+struct ThemeInner {
+    raw: NonNull<lv_theme_t>,
+    /// `None` for themes created by LVGL
+    data: Option<Box<ThemeData>>,
+    parent: Option<Theme>,
+}
+
+type ApplyCallback = dyn Fn(&mut ThemedWidget<'_>, WidgetClass);
+
+struct ThemeData {
+    apply: Box<ApplyCallback>,
+    /// Styles added by the callback, they are referenced by the widgets
+    styles: RefCell<Vec<StyleHandle>>,
+}
+
+impl Theme {
+    /// Creates a theme that calls `apply` for every new widget, after the parent theme
+    ///
+    /// The fonts and colors are copied from the parent theme.
+    pub fn new<F>(parent: Option<&Theme>, apply: F) -> Self
+    where
+        F: Fn(&mut ThemedWidget<'_>, WidgetClass) + 'static,
+    {
+        unsafe {
+            let raw = NonNull::new(lightvgl_sys::lv_theme_create()).unwrap();
+            if let Some(parent) = parent {
+                lightvgl_sys::lv_theme_copy(raw.as_ptr(), parent.raw());
+                lightvgl_sys::lv_theme_set_parent(raw.as_ptr(), parent.raw_mut());
+            }
+            let data = Box::new(ThemeData {
+                apply: Box::new(apply),
+                styles: RefCell::new(Vec::new()),
+            });
+            (*raw.as_ptr()).user_data = ::core::ptr::from_ref(&*data).cast_mut().cast();
+            lightvgl_sys::lv_theme_set_apply_cb(raw.as_ptr(), Some(apply_theme_trampoline));
+            Self {
+                inner: Arc::new(ThemeInner {
+                    raw,
+                    data: Some(data),
+                    parent: parent.cloned(),
+                }),
+            }
+        }
+    }
+
+    /// Returns the current theme of the display, e.g. the built-in default theme
+    pub fn from_display(display: &Display) -> Option<Self> {
+        unsafe {
+            let raw = NonNull::new(lightvgl_sys::lv_display_get_theme(display.raw().cast_mut()))?;
+            Some(Self {
+                inner: Arc::new(ThemeInner {
+                    raw,
+                    data: None,
+                    parent: None,
+                }),
+            })
+        }
+    }
+
+    #[inline]
+    pub fn raw(&self) -> *const lv_theme_t {
+        self.inner.raw.as_ptr().cast_const()
+    }
+
+    #[inline]
+    pub fn raw_mut(&self) -> *mut lv_theme_t {
+        self.inner.raw.as_ptr()
+    }
+
+    /// Returns whether both handles refer to the same theme
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Moves the styles retained by this theme and its parents into `styles`
+    fn take_styles(&self, styles: &mut Vec<StyleHandle>) {
+        let mut theme = Some(self);
+        while let Some(current) = theme {
+            if let Some(data) = &current.inner.data {
+                styles.append(&mut data.styles.borrow_mut());
+            }
+            theme = current.inner.parent.as_ref();
+        }
+    }
+}
+
+impl Drop for ThemeInner {
+    fn drop(&mut self) {
+        if self.data.is_some() {
+            unsafe {
+                lightvgl_sys::lv_theme_delete(self.raw.as_ptr());
+            }
+        }
+    }
+}
+
+/// The class of the widget a theme is applied to
+#[derive(Copy, Clone)]
+pub struct WidgetClass(&'static lv_obj_class_t);
+
+impl WidgetClass {
+    /// Returns whether the widget is exactly a `W`, e.g. `class.is::<Button<Wdg>>()`
+    #[inline]
+    pub fn is<W: WidgetSpec>(self) -> bool {
+        ::core::ptr::eq(self.0, W::get_class())
+    }
+
+    /// Returns the name of the class, e.g. `lv_button`, or `None` for unnamed custom classes
+    #[inline]
+    pub fn name(self) -> Option<&'static CStr> {
+        if self.0.name.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(self.0.name) })
+    }
+
+    #[inline]
+    pub fn raw(self) -> &'static lv_obj_class_t {
+        self.0
+    }
+}
+
+/// The widget passed to the apply callback of a [`Theme`]
+pub struct ThemedWidget<'a> {
+    widget: Wdg,
+    styles: &'a RefCell<Vec<StyleHandle>>,
+}
+
+impl ThemedWidget<'_> {
+    /// Adds a shared style to the widget, the theme keeps the style alive
+    pub fn add_style(&mut self, style: &StyleHandle) {
+        let mut styles = self.styles.borrow_mut();
+        if !styles.iter().any(|retained| retained.ptr_eq(style)) {
+            styles.push(style.clone());
+        }
+        let (raw, selector) = style.raw_and_selector();
+        unsafe {
+            lightvgl_sys::lv_obj_add_style(self.widget.raw_mut(), raw, selector);
+        }
+    }
+}
+
+impl Deref for ThemedWidget<'_> {
+    type Target = Wdg;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for ThemedWidget<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+unsafe extern "C" fn apply_theme_trampoline(theme: *mut lv_theme_t, obj: *mut lv_obj_t) {
+    unsafe {
+        let user_data = (*theme).user_data;
+        if user_data.is_null() {
+            crate::warn!("Theme user data was null, this should never happen!");
+            return;
+        }
+        let data = &*user_data.cast::<ThemeData>();
+        let class = WidgetClass(&*lightvgl_sys::lv_obj_get_class(obj));
+        let mut widget = ThemedWidget {
+            widget: Wdg::from_ptr(obj),
+            styles: &data.styles,
+        };
+        (data.apply)(&mut widget, class);
+    }
+}
+
+/// Applies the current theme of every display to all of its widgets again
+///
+/// `lv_theme_apply()` removes every style of the widgets, so the [`Style`](crate::styles::Style),
+/// [`StyleHandle`] and style property components of the entities are added back afterwards.
+/// Styles added to widgets outside the ECS are lost. Styles retained by the themes are
+/// released unless the apply callbacks add them again.
+pub fn reapply_theme(world: &mut World) {
+    // dropped only after no widget refers to them anymore
+    let mut retired_themes = Vec::new();
+    let mut retired_styles = Vec::new();
+    unsafe {
+        let mut display = lightvgl_sys::lv_display_get_next(::core::ptr::null_mut());
+        while !display.is_null() {
+            let (theme, replaced) = Display::from_ptr(display).take_themes();
+            if let Some(theme) = theme {
+                theme.take_styles(&mut retired_styles);
+            }
+            retired_themes.extend(replaced);
+            display = lightvgl_sys::lv_display_get_next(display);
+        }
+
+        for_each_obj(::core::ptr::null_mut(), |obj| {
+            lightvgl_sys::lv_theme_apply(obj);
+        });
+    }
+    crate::styles::reattach_styles(world);
+}
@@ -116,8 +116,26 @@
 //! let mut button_widget = button_entity.get::<Widget>().unwrap();
 //! assert_eq!(button_widget.get_child_count(), 1);
 //! ```
+//!
+//! #### Layers
+//! Widgets spawned with a [`Layer`] component are moved to that layer of the default display,
+//! so they stay visible when the screen changes.
+//! ```
+//! # use lv_bevy_ecs::widgets::{Label, Layer, LvglWorld};
+//! # use lv_bevy_ecs::functions::*;
+//! #
+//! # lv_bevy_ecs::setup_test_display!();
+//! #
+//! # let mut world = LvglWorld::default();
+//! let mut toast = Label::new();
+//! toast.set_text(c"Saved");
+//!
+//! world.spawn((toast.into_inner(), Layer::Top));
+//! assert_eq!(lv_layer_top().unwrap().get_child_count(), 1);
+//! ```
 
 use ::core::{
+    ffi::c_void,
     ops::{Deref, DerefMut},
     ptr::NonNull,
 };
@@ -130,16 +148,16 @@ use bevy_ecs::{
     lifecycle::Insert,
     observer::On,
     system::{ParamSet, Query},
-    world::World,
+    world::{EntityWorldMut, World},
 };
-#[cfg(feature = "no_ecs")]
-use lightvgl_sys::lv_style_selector_t;
 use lightvgl_sys::{lv_obj_class_t, lv_obj_get_class, lv_obj_t};
 use thiserror::Error;
 
+use crate::display::Display;
 use crate::events::{Event, EventCode};
+use crate::screens::Screen;
 #[cfg(feature = "no_ecs")]
-use crate::styles::Style;
+use crate::styles::{Selector, Style};
 
 /// An [`LvglWorld`] newtype that is `const` compatible, but it must be initalized manually before first use using `.init()`
 ///
@@ -188,7 +206,7 @@ pub struct LvglWorld(World);
 impl Default for LvglWorld {
     fn default() -> Self {
         let mut world = World::new();
-        world.add_observer(on_insert_parent);
+        add_observers(&mut world);
         Self(world)
     }
 }
@@ -213,6 +231,49 @@ impl LvglWorld {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Spawns the display as an entity with its active screen as a child [`Screen`] entity
+    ///
+    /// Despawning the display deletes it together with its screens and widgets.
+    ///
+    /// ```
+    /// # use lv_bevy_ecs::display::Display;
+    /// # use lv_bevy_ecs::screens::Screen;
+    /// # use lv_bevy_ecs::widgets::*;
+    /// # use bevy_ecs::hierarchy::{ChildOf, Children};
+    /// #
+    /// # lv_bevy_ecs::functions::lv_init();
+    /// let mut world = LvglWorld::default();
+    /// let display = world.spawn_display(Display::new(320, 240)).id();
+    /// let screen = world.get::<Children>(display).unwrap()[0];
+    /// assert!(world.get::<Screen>(screen).is_some());
+    ///
+    /// let label = Label::new().into_inner();
+    /// world.spawn((label, ChildOf(screen)));
+    /// world.despawn(display);
+    /// lv_bevy_ecs::functions::lv_timer_handler();
+    /// ```
+    #[inline]
+    pub fn spawn_display(&mut self, display: Display) -> EntityWorldMut<'_> {
+        spawn_display(self, display)
+    }
+}
+
+/// Registers the observers that keep the LVGL object tree in sync with the entity hierarchy
+pub(crate) fn add_observers(world: &mut World) {
+    world.add_observer(on_insert_parent);
+    world.add_observer(on_insert_layer);
+}
+
+pub(crate) fn spawn_display(world: &mut World, mut display: Display) -> EntityWorldMut<'_> {
+    let screen = display
+        .get_screen_active()
+        .map(|mut screen| Widget::from_ptr(screen.raw_mut()).unwrap());
+    let mut entity = world.spawn(display);
+    if let Some(screen) = screen {
+        entity.with_child((screen, Screen));
+    }
+    entity
 }
 
 #[derive(Component, PartialEq)]
@@ -264,11 +325,18 @@ impl Drop for Widget {
         unsafe {
             crate::info!("Dropping Obj");
             // Async is needed to prevent double-freeing child objects
-            lightvgl_sys::lv_obj_delete_async(self.raw.as_ptr());
+            lightvgl_sys::lv_async_call(Some(delete_obj), self.raw.as_ptr().cast());
         }
     }
 }
 
+/// Same as `lv_obj_delete_async()`, but can be cancelled when a display deletes the object first
+pub(crate) unsafe extern "C" fn delete_obj(obj: *mut c_void) {
+    unsafe {
+        lightvgl_sys::lv_obj_delete(obj.cast());
+    }
+}
+
 pub type Obj = Widget;
 
 #[derive(Debug, Error)]
@@ -290,7 +358,11 @@ fn on_insert_parent(
 ) {
     let event = trigger.event();
     let parent_widget = set.p1().get_mut(event.entity).unwrap().1.0;
-    let parent_ptr = set.p0().get_mut(parent_widget).unwrap().raw_mut();
+    // screens are children of the display entity, they have no parent object
+    let Ok(mut parent) = set.p0().get_mut(parent_widget) else {
+        return;
+    };
+    let parent_ptr = parent.raw_mut();
     let child_ptr = set.p1().get_mut(event.entity).unwrap().0.raw_mut();
     unsafe {
         lightvgl_sys::lv_obj_set_parent(child_ptr, parent_ptr);
@@ -298,6 +370,49 @@ fn on_insert_parent(
     crate::info!("On Insert Parent");
 }
 
+/// Moves the widget of the entity to a layer of the default display
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Layer {
+    /// Below every screen
+    Bottom,
+    /// Above every screen
+    Top,
+    /// Above the top layer
+    Sys,
+}
+
+impl Layer {
+    /// Returns the layer object of the default display
+    #[inline]
+    pub fn get(self) -> Option<Wdg> {
+        unsafe { Wdg::try_from_ptr(self.raw()) }
+    }
+
+    unsafe fn raw(self) -> *mut lv_obj_t {
+        unsafe {
+            match self {
+                Self::Bottom => lightvgl_sys::lv_layer_bottom(),
+                Self::Top => lightvgl_sys::lv_layer_top(),
+                Self::Sys => lightvgl_sys::lv_layer_sys(),
+            }
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value)]
+fn on_insert_layer(trigger: On<Insert, Layer>, mut widgets: Query<(&mut Widget, &Layer)>) {
+    let Ok((mut widget, layer)) = widgets.get_mut(trigger.event().entity) else {
+        return;
+    };
+    unsafe {
+        let layer_ptr = layer.raw();
+        if !layer_ptr.is_null() {
+            lightvgl_sys::lv_obj_set_parent(widget.raw_mut(), layer_ptr);
+        }
+    }
+    crate::info!("On Insert Layer");
+}
+
 /// Represents a borrowed Widget.
 #[derive(PartialEq)]
 pub struct Wdg {
@@ -361,8 +476,10 @@ impl Wdg {
     ///
     /// For example `Box::leak(Box::new(style))` can be used to prevent dropping it.
     #[inline]
-    pub unsafe fn add_style(&mut self, style: &mut Style, selector: lv_style_selector_t) {
-        unsafe { lightvgl_sys::lv_obj_add_style(self.raw_mut(), style.raw_mut(), selector) }
+    pub unsafe fn add_style(&mut self, style: &mut Style, selector: impl Into<Selector>) {
+        unsafe {
+            lightvgl_sys::lv_obj_add_style(self.raw_mut(), style.raw_mut(), selector.into().bits())
+        }
     }
 
     #[cfg(feature = "no_ecs")]